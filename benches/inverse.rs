@@ -0,0 +1,72 @@
+// Compares solving many inverse problems on the same ellipsoid with the free
+// inverse, deriving the ellipsoid's constants and reducing both latitudes for
+// each problem, against an InverseContext that derives the constants once and,
+// in a batch, reduces a latitude once while its point is repeated.
+use auxillary_sphere::{
+    earth::ellipsoid::WGS84,
+    geodesy::{
+        latlng::LatLng,
+        point_to_point::vincenty::{inverse, InverseContext},
+        problems::InverseProblem,
+        vincenty::GeodeticAccuracy,
+    },
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+fn pairs() -> Vec<(LatLng, LatLng)> {
+    (0..100)
+        .map(|i| {
+            let t = i as f64;
+            (
+                LatLng::new(-60.0 + 1.2 * t, -170.0 + 3.4 * t),
+                LatLng::new(70.0 - 1.3 * t, 20.0 + 1.5 * t),
+            )
+        })
+        .collect()
+}
+
+// From one point to many, as for the distances to points of interest.
+fn from_london() -> Vec<(LatLng, LatLng)> {
+    let london = LatLng::new(51.5007, -0.1246);
+    pairs().into_iter().map(|(_, y)| (london, y)).collect()
+}
+
+fn bench_group(c: &mut Criterion, name: &str, pairs: &[(LatLng, LatLng)]) {
+    let accuracy = GeodeticAccuracy::default();
+    let context = InverseContext::new(&WGS84, accuracy).unwrap();
+
+    let mut group = c.benchmark_group(name);
+    group.bench_function("free inverse", |b| {
+        b.iter(|| {
+            for &(x, y) in black_box(pairs) {
+                black_box(inverse(&WGS84, &accuracy, &InverseProblem { x, y }).unwrap());
+            }
+        })
+    });
+    group.bench_function("InverseContext::inverse", |b| {
+        b.iter(|| {
+            for &(x, y) in black_box(pairs) {
+                black_box(context.inverse(&InverseProblem { x, y }).unwrap());
+            }
+        })
+    });
+    group.bench_function("InverseContext::inverse_pairs", |b| {
+        b.iter(|| black_box(context.inverse_pairs(black_box(pairs))))
+    });
+    group.bench_function("InverseContext::distance_pairs", |b| {
+        b.iter(|| black_box(context.distance_pairs(black_box(pairs))))
+    });
+    group.finish();
+}
+
+fn bench_inverse(c: &mut Criterion) {
+    bench_group(c, "vincenty inverse of 100 pairs", &pairs());
+    bench_group(
+        c,
+        "vincenty inverse from London to 100 points",
+        &from_london(),
+    );
+}
+
+criterion_group!(benches, bench_inverse);
+criterion_main!(benches);
@@ -1,7 +1,7 @@
 use crate::units::*;
-use std::fmt;
+use std::{f64::consts::PI, fmt};
 
-#[derive(Copy, Clone)]
+#[derive(Debug, Copy, Clone)]
 pub struct Ellipsoid {
     pub equatorial_r: Radius,
     pub recip_f: f64,
@@ -14,6 +14,122 @@ impl fmt::Display for Ellipsoid {
     }
 }
 
+impl Ellipsoid {
+    /// The first eccentricity, `e = √(ƒ(2 - ƒ))`.
+    pub fn eccentricity(&self) -> f64 {
+        self.eccentricity_sq().sqrt()
+    }
+
+    /// The first eccentricity squared, `e² = ƒ(2 - ƒ)`.
+    pub fn eccentricity_sq(&self) -> f64 {
+        let f = flattening(self);
+        f * (2.0 - f)
+    }
+
+    /// The second eccentricity, `e' = e / (1 - ƒ)`.
+    pub fn second_eccentricity(&self) -> f64 {
+        self.second_eccentricity_sq().sqrt()
+    }
+
+    /// The second eccentricity squared, `e'² = e² / (1 - e²)`.
+    pub fn second_eccentricity_sq(&self) -> f64 {
+        let f = flattening(self);
+        self.eccentricity_sq() / ((1.0 - f) * (1.0 - f))
+    }
+
+    /// The third flattening, `n = (a - b) / (a + b) = ƒ / (2 - ƒ)`.
+    pub fn third_flattening(&self) -> f64 {
+        let f = flattening(self);
+        f / (2.0 - f)
+    }
+
+    /// The mean radius, `R₁ = (2a + b) / 3`.
+    ///
+    /// ```
+    /// # use auxillary_sphere::{earth::ellipsoid::WGS84, units::{Meter, Radius}};
+    /// let Radius(Meter(r1)) = WGS84.mean_r();
+    /// assert!((r1 - 6371008.7714).abs() < 0.0001);
+    /// ```
+    pub fn mean_r(&self) -> Radius {
+        let Radius(Meter(a)) = self.equatorial_r;
+        let Radius(Meter(b)) = polar_r(self);
+        Radius(Meter((2.0 * a + b) / 3.0))
+    }
+
+    /// The authalic radius, `R₂`, of the sphere with the same surface area.
+    ///
+    /// ```
+    /// # use auxillary_sphere::{earth::ellipsoid::WGS84, units::{Meter, Radius}};
+    /// let Radius(Meter(r2)) = WGS84.authalic_r();
+    /// assert!((r2 - 6371007.1809).abs() < 0.0001);
+    /// ```
+    pub fn authalic_r(&self) -> Radius {
+        let Radius(Meter(a)) = self.equatorial_r;
+        let Radius(Meter(b)) = polar_r(self);
+        let e = self.eccentricity();
+        if e == 0.0 {
+            self.equatorial_r
+        } else {
+            Radius(Meter(((a * a + b * b * e.atanh() / e) / 2.0).sqrt()))
+        }
+    }
+
+    /// The volumetric radius, `R₃ = ∛(a²b)`, of the sphere with the same
+    /// volume.
+    ///
+    /// ```
+    /// # use auxillary_sphere::{earth::ellipsoid::WGS84, units::{Meter, Radius}};
+    /// let Radius(Meter(r3)) = WGS84.volumetric_r();
+    /// assert!((r3 - 6371000.7900).abs() < 0.0001);
+    /// ```
+    pub fn volumetric_r(&self) -> Radius {
+        let Radius(Meter(a)) = self.equatorial_r;
+        let Radius(Meter(b)) = polar_r(self);
+        Radius(Meter((a * a * b).cbrt()))
+    }
+
+    /// The length of the meridian from the equator to a pole, from the series
+    /// in the third flattening, `n`, accurate to `n⁸`.
+    ///
+    /// ```
+    /// # use auxillary_sphere::{earth::ellipsoid::WGS84, units::Meter};
+    /// let Meter(q) = WGS84.quarter_meridian();
+    /// assert!((q - 10001965.7293).abs() < 0.0001);
+    /// ```
+    pub fn quarter_meridian(&self) -> Meter {
+        let Radius(Meter(a)) = self.equatorial_r;
+        let n = self.third_flattening();
+        let n2 = n * n;
+        let series =
+            1.0 + n2 * (1.0 / 4.0 + n2 * (1.0 / 64.0 + n2 * (1.0 / 256.0 + n2 * 25.0 / 16384.0)));
+        Meter(a / (1.0 + n) * series * PI / 2.0)
+    }
+
+    /// The meridional radius of curvature, `M`, at a latitude.
+    pub fn meridional_r(&self, Rad(lat): Rad) -> Radius {
+        let Radius(Meter(a)) = self.equatorial_r;
+        let e2 = self.eccentricity_sq();
+        let w2 = 1.0 - e2 * lat.sin() * lat.sin();
+        Radius(Meter(a * (1.0 - e2) / (w2 * w2.sqrt())))
+    }
+
+    /// The prime vertical radius of curvature, `N`, at a latitude.
+    ///
+    /// ```
+    /// # use auxillary_sphere::{earth::ellipsoid::WGS84, units::{Meter, Rad, Radius}};
+    /// // At a pole the radii of curvature are equal, a² / b.
+    /// let pole = Rad(std::f64::consts::FRAC_PI_2);
+    /// let Radius(Meter(m)) = WGS84.meridional_r(pole);
+    /// let Radius(Meter(n)) = WGS84.prime_vertical_r(pole);
+    /// assert!((m - n).abs() < 1e-6);
+    /// ```
+    pub fn prime_vertical_r(&self, Rad(lat): Rad) -> Radius {
+        let Radius(Meter(a)) = self.equatorial_r;
+        let e2 = self.eccentricity_sq();
+        Radius(Meter(a / (1.0 - e2 * lat.sin() * lat.sin()).sqrt()))
+    }
+}
+
 pub fn flattening(e: &Ellipsoid) -> f64 {
     1.0 / e.recip_f
 }
@@ -22,6 +138,13 @@ pub fn polar_r(e: &Ellipsoid) -> Radius {
     e.equatorial_r * (1.0 - flattening(e))
 }
 
+/// An ellipsoid is valid with a finite positive equatorial radius and an
+/// oblate flattening, `0 <= ƒ < 1`, where a sphere has `1/ƒ = ∞`.
+pub fn is_valid(e: &Ellipsoid) -> bool {
+    let Radius(Meter(a)) = e.equatorial_r;
+    a.is_finite() && a > 0.0 && e.recip_f > 1.0
+}
+
 /// SEE: <https://en.wikipedia.org/wiki/World_Geodetic_System>
 /// <https://en.wikipedia.org/wiki/World_Geodetic_System#A_new_World_Geodetic_System:_WGS_84>
 pub static WGS84: Ellipsoid = Ellipsoid {
@@ -52,6 +175,14 @@ pub static HAYFORD: Ellipsoid = Ellipsoid {
     recip_f: 297.0,
 };
 
+/// The Airy 1830 ellipsoid of the Ordnance Survey of Great Britain, with
+/// a = 6377563.396m and b = 6356256.909m. SEE:
+/// <https://www.ordnancesurvey.co.uk/documents/resources/guide-coordinate-systems-great-britain.pdf>
+pub static AIRY: Ellipsoid = Ellipsoid {
+    equatorial_r: Radius(Meter(6377563.396)),
+    recip_f: 299.3249646,
+};
+
 /// Clarke's 1866 ellipsoid approximated in metres. "Clarke actually defined his
 /// 1866 spheroid as a = 20,926,062 British feet, b = 20,855,121 British feet"
 /// SEE: <https://en.wikipedia.org/wiki/North_American_Datum>
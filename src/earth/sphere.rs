@@ -0,0 +1,54 @@
+use super::ellipsoid::Ellipsoid;
+use crate::units::{Meter, Radius};
+
+pub static EARTH_RADIUS: Radius = Radius(Meter(6371000.0));
+
+/// A sphere as a model of the earth, a choice of radius.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Sphere {
+    pub radius: Radius,
+}
+
+impl Sphere {
+    /// The sphere of the FAI Sporting Code, with a radius of 6371 km.
+    pub fn fai() -> Self {
+        FAI_SPHERE
+    }
+
+    /// The sphere with the mean radius, `R₁`, of the ellipsoid.
+    ///
+    /// ```
+    /// # use auxillary_sphere::{earth::{ellipsoid::WGS84, sphere::Sphere}, units::*};
+    /// let Radius(Meter(r)) = Sphere::mean(&WGS84).radius;
+    /// assert!((r - 6371008.7714).abs() < 0.0001);
+    /// ```
+    pub fn mean(ellipsoid: &Ellipsoid) -> Self {
+        Sphere {
+            radius: ellipsoid.mean_r(),
+        }
+    }
+
+    /// The sphere with the same surface area as the ellipsoid.
+    pub fn authalic(ellipsoid: &Ellipsoid) -> Self {
+        Sphere {
+            radius: ellipsoid.authalic_r(),
+        }
+    }
+
+    /// The sphere with the same volume as the ellipsoid.
+    pub fn volumetric(ellipsoid: &Ellipsoid) -> Self {
+        Sphere {
+            radius: ellipsoid.volumetric_r(),
+        }
+    }
+}
+
+impl Default for Sphere {
+    fn default() -> Self {
+        FAI_SPHERE
+    }
+}
+
+pub static FAI_SPHERE: Sphere = Sphere {
+    radius: EARTH_RADIUS,
+};
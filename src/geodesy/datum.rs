@@ -0,0 +1,423 @@
+// Datum transformations, moving positions from the ellipsoid and origin of one
+// datum to those of another.
+//
+// The Helmert transformation is a similarity transform of the Earth-centred
+// Earth-fixed coordinates, a translation, a scale and small rotations about
+// each axis. Rotations follow the position vector convention, positive
+// anticlockwise when looking towards the origin along the axis, as used by the
+// Ordnance Survey and in the EPSG dataset under method 9606.
+//
+// The Molodensky transformations shift the latitude, longitude and height
+// directly for the translation and the change in the ellipsoid, without going
+// through Cartesian coordinates. They leave out the rotations and scale. The
+// abridged form also leaves out terms in the height and in products of the
+// flattening.
+//
+// SEE: <https://www.ordnancesurvey.co.uk/documents/resources/guide-coordinate-systems-great-britain.pdf>
+// and DMA TR 8350.2, Department of Defense World Geodetic System 1984, 1997.
+use crate::{
+    earth::ellipsoid::{self, flattening, is_valid, polar_r, Ellipsoid},
+    units::{
+        convert::{deg_to_rad, plus_minus_pi_rad, sec_to_deg},
+        Meter, Rad, Radius, Sec,
+    },
+};
+use std::f64::consts::FRAC_PI_2;
+
+use super::{
+    ecef::{from_ecef, to_ecef, Ecef},
+    error::GeodesyError,
+    latlng::LatLngAlt,
+    vincenty::check_lat,
+};
+
+/// The seven parameters of a Helmert transformation, the translation of the
+/// origin, the scale change in parts per million and the rotations about the
+/// X, Y and Z axes in the position vector convention.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Helmert {
+    pub tx: Meter,
+    pub ty: Meter,
+    pub tz: Meter,
+    pub s: f64,
+    pub rx: Sec,
+    pub ry: Sec,
+    pub rz: Sec,
+}
+
+/// The transformation that leaves coordinates unchanged.
+pub static IDENTITY: Helmert = Helmert {
+    tx: Meter(0.0),
+    ty: Meter(0.0),
+    tz: Meter(0.0),
+    s: 0.0,
+    rx: Sec(0.0),
+    ry: Sec(0.0),
+    rz: Sec(0.0),
+};
+
+impl Helmert {
+    // The translation and the matrix (1 + s) R, with R the rotation matrix for
+    // small angles.
+    fn matrix(&self) -> ([f64; 3], [[f64; 3]; 3]) {
+        let rad = |sec: Sec| deg_to_rad(sec_to_deg(sec)).0;
+        let (rx, ry, rz) = (rad(self.rx), rad(self.ry), rad(self.rz));
+        let k = 1.0 + self.s * 1e-6;
+
+        (
+            [self.tx.0, self.ty.0, self.tz.0],
+            [
+                [k, -k * rz, k * ry],
+                [k * rz, k, -k * rx],
+                [-k * ry, k * rx, k],
+            ],
+        )
+    }
+
+    /// Transforms Earth-centred Earth-fixed coordinates, X' = T + (1 + s) R X.
+    pub fn apply(&self, p: &Ecef) -> Ecef {
+        let (t, m) = self.matrix();
+        let x = [p.x.0, p.y.0, p.z.0];
+        let row = |i: usize| t[i] + m[i][0] * x[0] + m[i][1] * x[1] + m[i][2] * x[2];
+
+        Ecef {
+            x: Meter(row(0)),
+            y: Meter(row(1)),
+            z: Meter(row(2)),
+        }
+    }
+
+    /// Reverses the transformation exactly, X = ((1 + s) R)⁻¹ (X' - T), rather
+    /// than by negating the parameters, which is only good to first order.
+    pub fn reverse(&self, p: &Ecef) -> Ecef {
+        let (t, m) = self.matrix();
+        let x = [p.x.0 - t[0], p.y.0 - t[1], p.z.0 - t[2]];
+
+        // The inverse from the adjugate, the transposed cofactors.
+        let cofactor = |i: usize, j: usize| {
+            let (r0, r1) = ((i + 1) % 3, (i + 2) % 3);
+            let (c0, c1) = ((j + 1) % 3, (j + 2) % 3);
+            m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0]
+        };
+        let det = (0..3).map(|j| m[0][j] * cofactor(0, j)).sum::<f64>();
+        let row = |i: usize| (0..3).map(|j| cofactor(j, i) * x[j]).sum::<f64>() / det;
+
+        Ecef {
+            x: Meter(row(0)),
+            y: Meter(row(1)),
+            z: Meter(row(2)),
+        }
+    }
+}
+
+/// A geodetic datum, its ellipsoid and the Helmert transformation from its
+/// coordinates to those of WGS84.
+#[derive(Debug, Clone, Copy)]
+pub struct Datum {
+    pub ellipsoid: Ellipsoid,
+    pub to_wgs84: Helmert,
+}
+
+/// The World Geodetic System 1984.
+pub static WGS84: Datum = Datum {
+    ellipsoid: ellipsoid::WGS84,
+    to_wgs84: IDENTITY,
+};
+
+/// The Ordnance Survey Great Britain 1936 datum on the Airy 1830 ellipsoid,
+/// with the Ordnance Survey's transformation good to within a few metres.
+pub static OSGB36: Datum = Datum {
+    ellipsoid: ellipsoid::AIRY,
+    to_wgs84: Helmert {
+        tx: Meter(446.448),
+        ty: Meter(-125.157),
+        tz: Meter(542.060),
+        s: -20.4894,
+        rx: Sec(0.1502),
+        ry: Sec(0.2470),
+        rz: Sec(0.8421),
+    },
+};
+
+/// The European Datum 1950 on the International 1924 ellipsoid, with EPSG
+/// transformation 1311, ED50 to WGS 84 (18), the UKOOA common offshore
+/// transformation for the UK sector of the North Sea, good to within a few
+/// metres there. SEE: <https://epsg.io/1311>
+pub static ED50: Datum = Datum {
+    ellipsoid: ellipsoid::HAYFORD,
+    to_wgs84: Helmert {
+        tx: Meter(-89.5),
+        ty: Meter(-93.8),
+        tz: Meter(-123.1),
+        s: 1.2,
+        rx: Sec(0.0),
+        ry: Sec(0.0),
+        rz: Sec(-0.156),
+    },
+};
+
+fn check_datum(datum: &Datum) -> Result<(), GeodesyError> {
+    if is_valid(&datum.ellipsoid) {
+        Ok(())
+    } else {
+        Err(GeodesyError::InvalidEllipsoid)
+    }
+}
+
+/// Transforms a position between datums through Earth-centred Earth-fixed
+/// coordinates with the Helmert transformation of each datum to WGS84.
+///
+/// ```
+/// # use auxillary_sphere::geodesy::{
+/// #     datum::*,
+/// #     haversines::distance,
+/// #     latlng::{LatLng, LatLngAlt},
+/// # };
+/// # use auxillary_sphere::units::{Meter, DMS};
+/// // Caister Water Tower, the worked example of the Ordnance Survey's guide,
+/// // at 52°39′28.8282″N 1°42′57.8663″E in ETRS89, taken as WGS84, and
+/// // published at 52°39′27.2531″N 1°43′04.5177″E in OSGB36.
+/// let etrs89 = LatLng::from((
+///     DMS { deg: 52, min: 39, sec: 28.8282 },
+///     DMS { deg: 1, min: 42, sec: 57.8663 },
+/// ));
+/// let osgb36 = LatLng::from((
+///     DMS { deg: 52, min: 39, sec: 27.2531 },
+///     DMS { deg: 1, min: 43, sec: 4.5177 },
+/// ));
+/// let x = LatLngAlt {
+///     lat: etrs89.lat,
+///     lng: etrs89.lng,
+///     alt: Meter(108.05),
+/// };
+///
+/// // Within the few metres that the Helmert transformation is good to.
+/// let y = helmert(&WGS84, &OSGB36, &x).unwrap();
+/// assert!(distance(&y.latlng(), &osgb36).dist < 5.0);
+///
+/// // Reversed exactly.
+/// let z = helmert(&OSGB36, &WGS84, &y).unwrap();
+/// assert!((z.lat.0 - x.lat.0).abs() < 1e-14);
+/// assert!((z.lng.0 - x.lng.0).abs() < 1e-14);
+/// assert!((z.alt.0 - x.alt.0).abs() < 1e-7);
+/// ```
+pub fn helmert(from: &Datum, to: &Datum, p: &LatLngAlt) -> Result<LatLngAlt, GeodesyError> {
+    check_datum(to)?;
+    let ecef = from.to_wgs84.apply(&to_ecef(&from.ellipsoid, p)?);
+    from_ecef(&to.ellipsoid, &to.to_wgs84.reverse(&ecef))
+}
+
+// The shift between datums in Molodensky's transformations, the translation
+// between their origins and the changes in the equatorial radius and the
+// flattening.
+struct Shift {
+    dx: f64,
+    dy: f64,
+    dz: f64,
+    da: f64,
+    df: f64,
+}
+
+impl Shift {
+    fn new(from: &Datum, to: &Datum) -> Result<Self, GeodesyError> {
+        check_datum(from)?;
+        check_datum(to)?;
+
+        let Radius(Meter(a_from)) = from.ellipsoid.equatorial_r;
+        let Radius(Meter(a_to)) = to.ellipsoid.equatorial_r;
+        let (t_from, t_to) = (from.to_wgs84, to.to_wgs84);
+
+        Ok(Shift {
+            dx: t_from.tx.0 - t_to.tx.0,
+            dy: t_from.ty.0 - t_to.ty.0,
+            dz: t_from.tz.0 - t_to.tz.0,
+            da: a_to - a_from,
+            df: flattening(&to.ellipsoid) - flattening(&from.ellipsoid),
+        })
+    }
+}
+
+// The longitude to shift from. At a pole, where every longitude is the same
+// point and the shift in longitude is unbounded, this is the meridian along
+// which the shift of the origin across the polar axis moves the point, leaving
+// no shift in longitude.
+fn shift_lng(lat: f64, lng: Rad, dx: f64, dy: f64) -> Rad {
+    if lat.abs() == FRAC_PI_2 && (dx != 0.0 || dy != 0.0) {
+        Rad(dy.atan2(dx))
+    } else {
+        lng
+    }
+}
+
+// Applies the shifts in latitude, longitude and height.
+fn shifted(p: &LatLngAlt, lng: Rad, d_lat: f64, d_lng: f64, d_alt: f64) -> LatLngAlt {
+    LatLngAlt {
+        lat: Rad(p.lat.0 + d_lat),
+        lng: plus_minus_pi_rad(Rad(lng.0 + d_lng)),
+        alt: Meter(p.alt.0 + d_alt),
+    }
+}
+
+/// Transforms a position between datums with the standard Molodensky
+/// transformation, using only the translations of the datums, good to a few
+/// metres away from the poles for datums whose rotations are small.
+pub fn molodensky(from: &Datum, to: &Datum, p: &LatLngAlt) -> Result<LatLngAlt, GeodesyError> {
+    let Shift { dx, dy, dz, da, df } = Shift::new(from, to)?;
+    let Rad(lat) = check_lat(p.lat)?;
+    let Meter(h) = p.alt;
+
+    let ellipsoid = &from.ellipsoid;
+    let Radius(Meter(a)) = ellipsoid.equatorial_r;
+    let Radius(Meter(b)) = polar_r(ellipsoid);
+    let e2 = ellipsoid.eccentricity_sq();
+    let Radius(Meter(m)) = ellipsoid.meridional_r(Rad(lat));
+    let Radius(Meter(n)) = ellipsoid.prime_vertical_r(Rad(lat));
+
+    let lng = shift_lng(lat, p.lng, dx, dy);
+    let (sin_lat, cos_lat) = lat.sin_cos();
+    let (sin_lng, cos_lng) = lng.0.sin_cos();
+
+    let d_lat = (-dx * sin_lat * cos_lng - dy * sin_lat * sin_lng
+        + dz * cos_lat
+        + da * n * e2 * sin_lat * cos_lat / a
+        + df * (m * a / b + n * b / a) * sin_lat * cos_lat)
+        / (m + h);
+    let d_lng = if lat.abs() == FRAC_PI_2 {
+        0.0
+    } else {
+        (-dx * sin_lng + dy * cos_lng) / ((n + h) * cos_lat)
+    };
+    let d_alt = dx * cos_lat * cos_lng + dy * cos_lat * sin_lng + dz * sin_lat - da * a / n
+        + df * b / a * n * sin_lat * sin_lat;
+
+    Ok(shifted(p, lng, d_lat, d_lng, d_alt))
+}
+
+/// Transforms a position between datums with the abridged Molodensky
+/// transformation, as with [molodensky] but dropping the terms in the height
+/// and in products of the flattening.
+pub fn abridged_molodensky(
+    from: &Datum,
+    to: &Datum,
+    p: &LatLngAlt,
+) -> Result<LatLngAlt, GeodesyError> {
+    let Shift { dx, dy, dz, da, df } = Shift::new(from, to)?;
+    let Rad(lat) = check_lat(p.lat)?;
+
+    let ellipsoid = &from.ellipsoid;
+    let Radius(Meter(a)) = ellipsoid.equatorial_r;
+    let f = flattening(ellipsoid);
+    let Radius(Meter(m)) = ellipsoid.meridional_r(Rad(lat));
+    let Radius(Meter(n)) = ellipsoid.prime_vertical_r(Rad(lat));
+
+    let lng = shift_lng(lat, p.lng, dx, dy);
+    let (sin_lat, cos_lat) = lat.sin_cos();
+    let (sin_lng, cos_lng) = lng.0.sin_cos();
+    let flattening_shift = a * df + f * da;
+
+    let d_lat = (-dx * sin_lat * cos_lng - dy * sin_lat * sin_lng
+        + dz * cos_lat
+        + flattening_shift * (2.0 * lat).sin())
+        / m;
+    let d_lng = if lat.abs() == FRAC_PI_2 {
+        0.0
+    } else {
+        (-dx * sin_lng + dy * cos_lng) / (n * cos_lat)
+    };
+    let d_alt = dx * cos_lat * cos_lng
+        + dy * cos_lat * sin_lng
+        + dz * sin_lat
+        + flattening_shift * sin_lat * sin_lat
+        - da;
+
+    Ok(shifted(p, lng, d_lat, d_lng, d_alt))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geodesy::{karney, problems::InverseProblem};
+
+    // The distance on WGS84 between the positions.
+    fn miss(x: &LatLngAlt, y: &LatLngAlt) -> f64 {
+        let prob = InverseProblem {
+            x: x.latlng(),
+            y: y.latlng(),
+        };
+        karney::inverse(&ellipsoid::WGS84, &prob).unwrap().s.dist
+    }
+
+    #[test]
+    fn london() {
+        // Around London, WGS84 is about 120m from OSGB36, mostly to the west.
+        let x = LatLngAlt::new(51.5, -0.1, 0.0);
+        let y = helmert(&OSGB36, &WGS84, &x).unwrap();
+        let shift = miss(&x, &y);
+
+        assert!(y.lng.0 < x.lng.0);
+        assert!(shift > 100.0 && shift < 140.0);
+    }
+
+    #[test]
+    fn position_vector_rotation() {
+        // A positive rotation about Z turns the X axis towards the Y axis.
+        let rz = Helmert {
+            rz: Sec(1.0),
+            ..IDENTITY
+        };
+        let x = Ecef {
+            x: Meter(6378137.0),
+            y: Meter(0.0),
+            z: Meter(0.0),
+        };
+        let Ecef { y: Meter(y), .. } = rz.apply(&x);
+
+        assert!((y - 6378137.0 * deg_to_rad(sec_to_deg(Sec(1.0))).0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn reverse() {
+        let ecef = to_ecef(&ellipsoid::AIRY, &LatLngAlt::new(57.0, -4.0, 500.0)).unwrap();
+        let back = OSGB36.to_wgs84.reverse(&OSGB36.to_wgs84.apply(&ecef));
+
+        assert!((back.x.0 - ecef.x.0).abs() < 1e-8);
+        assert!((back.y.0 - ecef.y.0).abs() < 1e-8);
+        assert!((back.z.0 - ecef.z.0).abs() < 1e-8);
+    }
+
+    #[test]
+    fn molodensky_near_helmert() {
+        // ED50 has only a small rotation, about 4m at the surface, to leave
+        // out, but its scale changes the height by close to 8m.
+        for (lat, lng) in [(40.0, -3.7), (52.5, 13.4), (60.2, 24.9), (37.9, 23.7)] {
+            let x = LatLngAlt::new(lat, lng, 100.0);
+            let y = helmert(&ED50, &WGS84, &x).unwrap();
+            let standard = molodensky(&ED50, &WGS84, &x).unwrap();
+            let abridged = abridged_molodensky(&ED50, &WGS84, &x).unwrap();
+
+            assert!(miss(&y, &standard) < 5.0);
+            assert!(miss(&y, &abridged) < 5.0);
+            assert!(miss(&standard, &abridged) < 0.5);
+            assert!((standard.alt.0 - y.alt.0).abs() < 10.0);
+        }
+    }
+
+    #[test]
+    fn molodensky_at_poles() {
+        // The shift of the origin across the polar axis moves the pole along
+        // the meridian in its direction, as with the Helmert transformation,
+        // which for ED50 has no rotation to leave out there.
+        for lat in [90.0, -90.0] {
+            let x = LatLngAlt::new(lat, 30.0, 0.0);
+            let y = helmert(&ED50, &WGS84, &x).unwrap();
+            for z in [
+                molodensky(&ED50, &WGS84, &x).unwrap(),
+                abridged_molodensky(&ED50, &WGS84, &x).unwrap(),
+            ] {
+                assert!(z.lat.0.abs() < FRAC_PI_2);
+                assert!((z.lng.0 - y.lng.0).abs() < 1e-6);
+                assert!(miss(&y, &z) < 0.5);
+            }
+        }
+    }
+}
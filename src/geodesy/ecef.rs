@@ -0,0 +1,270 @@
+// Earth-centred Earth-fixed (ECEF) Cartesian coordinates, with the origin at
+// the centre of the ellipsoid, Z towards the north pole and X through the
+// prime meridian on the equator.
+//
+// The geodetic position is found from the Cartesian coordinates in closed
+// form with Vermeille's method, arranged as by Karney to keep the accuracy
+// for points inside the evolute of the ellipsoid, near its centre, and on the
+// polar axis.
+//
+// SEE: <https://doi.org/10.1007/s00190-010-0419-x> and
+// <https://geographiclib.sourceforge.io/C++/doc/classGeographicLib_1_1Geocentric.html>
+use crate::{
+    earth::ellipsoid::{is_valid, Ellipsoid},
+    units::{Meter, Rad, Radius},
+};
+use std::fmt;
+
+use super::{error::GeodesyError, latlng::LatLngAlt, vincenty::check_lat};
+
+/// A point in Earth-centred Earth-fixed coordinates.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Ecef {
+    pub x: Meter,
+    pub y: Meter,
+    pub z: Meter,
+}
+
+impl fmt::Display for Ecef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}m, {}m, {}m)", self.x.0, self.y.0, self.z.0)
+    }
+}
+
+/// The Earth-centred Earth-fixed coordinates of a position with the latitude
+/// rejected outside the range -90° .. 90°.
+///
+/// ```
+/// # use auxillary_sphere::{
+/// #     earth::ellipsoid::WGS84,
+/// #     geodesy::{ecef::*, latlng::LatLngAlt},
+/// # };
+/// let x = LatLngAlt::new(51.4778, -0.0015, 46.0);
+/// let y = from_ecef(&WGS84, &to_ecef(&WGS84, &x).unwrap()).unwrap();
+///
+/// assert!((y.lat.0 - x.lat.0).abs() < 1e-15);
+/// assert!((y.lng.0 - x.lng.0).abs() < 1e-15);
+/// assert!((y.alt.0 - x.alt.0).abs() < 1e-8);
+/// ```
+pub fn to_ecef(ellipsoid: &Ellipsoid, p: &LatLngAlt) -> Result<Ecef, GeodesyError> {
+    if !is_valid(ellipsoid) {
+        return Err(GeodesyError::InvalidEllipsoid);
+    }
+
+    let Rad(lat) = check_lat(p.lat)?;
+    let Radius(Meter(a)) = ellipsoid.equatorial_r;
+    let e2 = ellipsoid.eccentricity_sq();
+    let Meter(h) = p.alt;
+
+    let (sin_lat, cos_lat) = lat.sin_cos();
+    let (sin_lng, cos_lng) = p.lng.0.sin_cos();
+    let n = a / (1.0 - e2 * sin_lat * sin_lat).sqrt();
+    let r = (n + h) * cos_lat;
+
+    Ok(Ecef {
+        x: Meter(r * cos_lng),
+        y: Meter(r * sin_lng),
+        z: Meter((n * (1.0 - e2) + h) * sin_lat),
+    })
+}
+
+/// The position, with its height above the ellipsoid, of a point in
+/// Earth-centred Earth-fixed coordinates. The longitude is normalized to
+/// -180° .. 180°, taken as 0° on the polar axis.
+pub fn from_ecef(ellipsoid: &Ellipsoid, p: &Ecef) -> Result<LatLngAlt, GeodesyError> {
+    if !is_valid(ellipsoid) {
+        return Err(GeodesyError::InvalidEllipsoid);
+    }
+
+    let Radius(Meter(a)) = ellipsoid.equatorial_r;
+    let e2 = ellipsoid.eccentricity_sq();
+    let e2m = 1.0 - e2;
+    let e4 = e2 * e2;
+    let Ecef {
+        x: Meter(x),
+        y: Meter(y),
+        z: Meter(z),
+    } = *p;
+
+    // The distance from the polar axis.
+    let r = x.hypot(y);
+    let lng = y.atan2(x);
+
+    let p = (r / a) * (r / a);
+    let q = e2m * (z / a) * (z / a);
+    let rr = (p + q - e4) / 6.0;
+
+    let (sin_lat, cos_lat, h) = if e2 == 0.0 {
+        // On a sphere, where the general case divides by e², the latitude is
+        // geocentric and the centre is taken as nearest the north pole.
+        let (zz, xx) = if r == 0.0 && z == 0.0 {
+            (1.0, 0.0)
+        } else {
+            (z, r)
+        };
+        let hyp = zz.hypot(xx);
+        (zz / hyp, xx / hyp, r.hypot(z) - a)
+    } else if !(e4 * q == 0.0 && rr <= 0.0) {
+        // The cube root of Vermeille's cubic, taking the trigonometric
+        // solution when the discriminant is negative, inside the evolute.
+        let s = e4 * p * q / 4.0;
+        let rr2 = rr * rr;
+        let rr3 = rr * rr2;
+        let disc = s * (2.0 * rr3 + s);
+        let u = if disc >= 0.0 {
+            let t3 = s + rr3;
+            let t3 = t3 + if t3 < 0.0 { -disc.sqrt() } else { disc.sqrt() };
+            let t = t3.cbrt();
+            rr + t + if t != 0.0 { rr2 / t } else { 0.0 }
+        } else {
+            let ang = (-disc).sqrt().atan2(-(s + rr3));
+            rr + 2.0 * rr * (ang / 3.0).cos()
+        };
+
+        let v = (u * u + e4 * q).sqrt();
+        // u + v, without cancellation when u < 0.
+        let uv = if u < 0.0 { e4 * q / (v - u) } else { u + v };
+        let w = (e2 * (uv - q) / (2.0 * v)).max(0.0);
+        let k = uv / ((uv + w * w).sqrt() + w);
+        let k2 = k + e2;
+
+        let d = k * r / k2;
+        let hyp = (z / k).hypot(r / k2);
+        ((z / k) / hyp, (r / k2) / hyp, (1.0 - e2m / k) * d.hypot(z))
+    } else {
+        // On the equatorial plane inside the evolute, where the nearest point
+        // of the ellipsoid is off the equator.
+        let zz = ((e4 - p) / e2m).sqrt();
+        let xx = p.sqrt();
+        let hyp = zz.hypot(xx);
+        let sin_lat = if z < 0.0 { -zz / hyp } else { zz / hyp };
+        (sin_lat, xx / hyp, -a * e2m * hyp / e2)
+    };
+
+    Ok(LatLngAlt {
+        lat: Rad(sin_lat.atan2(cos_lat)),
+        lng: Rad(lng),
+        alt: Meter(h),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        earth::ellipsoid::{polar_r, AIRY, WGS84},
+        geodesy::latlng::LatLng,
+        units::convert::deg_to_rad,
+        units::{Deg, DMS},
+    };
+
+    #[test]
+    fn axes() {
+        let Radius(Meter(a)) = WGS84.equatorial_r;
+        let Radius(Meter(b)) = polar_r(&WGS84);
+        for (p, x, y, z) in [
+            (LatLngAlt::new(0.0, 0.0, 0.0), a, 0.0, 0.0),
+            (LatLngAlt::new(0.0, 90.0, 100.0), 0.0, a + 100.0, 0.0),
+            (LatLngAlt::new(90.0, 0.0, 0.0), 0.0, 0.0, b),
+            (LatLngAlt::new(-90.0, 0.0, -b), 0.0, 0.0, 0.0),
+        ] {
+            let ecef = to_ecef(&WGS84, &p).unwrap();
+            assert!((ecef.x.0 - x).abs() < 1e-9);
+            assert!((ecef.y.0 - y).abs() < 1e-9);
+            assert!((ecef.z.0 - z).abs() < 1e-9);
+        }
+
+        // The centre is nearest the poles, at a depth of b.
+        let centre = Ecef {
+            x: Meter(0.0),
+            y: Meter(0.0),
+            z: Meter(0.0),
+        };
+        let p = from_ecef(&WGS84, &centre).unwrap();
+        assert!((p.lat.0 - deg_to_rad(Deg(90.0)).0).abs() < 1e-15);
+        assert!((p.alt.0 + b).abs() < 1e-9);
+    }
+
+    #[test]
+    fn round_trip() {
+        for lat in (-90..=90)
+            .step_by(5)
+            .map(|d| d as f64)
+            .chain([89.9999, -0.0001])
+        {
+            for alt in [-6e6, -1e4, 0.0, 1e3, 2e7, 4e8] {
+                let x = LatLngAlt::new(lat, 123.0, alt);
+                let y = from_ecef(&WGS84, &to_ecef(&WGS84, &x).unwrap()).unwrap();
+                assert!((y.lat.0 - x.lat.0).abs() < 1e-14, "{lat}° at {alt}m");
+                assert!((y.lng.0 - x.lng.0).abs() < 1e-14, "{lat}° at {alt}m");
+                assert!((y.alt.0 - alt).abs() < 1e-8 * alt.abs().max(1.0));
+            }
+        }
+    }
+
+    #[test]
+    fn os_annex_b() {
+        // SEE: A guide to coordinate systems in Great Britain, Annex B,
+        // <https://www.ordnancesurvey.co.uk/documents/resources/guide-coordinate-systems-great-britain.pdf>
+        let LatLng { lat, lng } = LatLng::from((
+            DMS {
+                deg: 52,
+                min: 39,
+                sec: 27.2531,
+            },
+            DMS {
+                deg: 1,
+                min: 43,
+                sec: 4.5177,
+            },
+        ));
+        let p = LatLngAlt {
+            lat,
+            lng,
+            alt: Meter(24.7),
+        };
+        let ecef = to_ecef(&AIRY, &p).unwrap();
+        assert!((ecef.x.0 - 3874938.849).abs() < 1e-3);
+        assert!((ecef.y.0 - 116218.624).abs() < 1e-3);
+        assert!((ecef.z.0 - 5047168.208).abs() < 1e-3);
+
+        // Back from the published coordinates, to the published 0.0001″.
+        let published = Ecef {
+            x: Meter(3874938.849),
+            y: Meter(116218.624),
+            z: Meter(5047168.208),
+        };
+        let q = from_ecef(&AIRY, &published).unwrap();
+        assert!((q.lat.0 - p.lat.0).abs() < 5e-10);
+        assert!((q.lng.0 - p.lng.0).abs() < 5e-10);
+        assert!((q.alt.0 - p.alt.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn sphere() {
+        let sphere = Ellipsoid {
+            equatorial_r: WGS84.equatorial_r,
+            recip_f: f64::INFINITY,
+        };
+        let Radius(Meter(a)) = sphere.equatorial_r;
+        let centre = Ecef {
+            x: Meter(0.0),
+            y: Meter(0.0),
+            z: Meter(0.0),
+        };
+        let p = from_ecef(&sphere, &centre).unwrap();
+        assert_eq!(p.lat, deg_to_rad(Deg(90.0)));
+        assert_eq!(p.alt, Meter(-a));
+
+        for x in [
+            LatLngAlt::new(0.0, 10.0, 0.0),
+            LatLngAlt::new(45.0, -120.0, 1e3),
+            LatLngAlt::new(-90.0, 0.0, -a / 2.0),
+        ] {
+            let y = from_ecef(&sphere, &to_ecef(&sphere, &x).unwrap()).unwrap();
+            assert!((y.lat.0 - x.lat.0).abs() < 1e-15);
+            assert!((y.lng.0 - x.lng.0).abs() < 1e-15);
+            assert!((y.alt.0 - x.alt.0).abs() < 1e-8);
+        }
+    }
+}
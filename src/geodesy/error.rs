@@ -0,0 +1,44 @@
+use crate::units::Deg;
+use std::fmt;
+
+/// The ways that solving a geodesy problem can fail.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GeodesyError {
+    /// A latitude outside of the -90° .. 90° range.
+    LatitudeOutOfRange(Deg),
+    /// The points are antipodal or nearly so and the solution didn't converge.
+    Antipodal,
+    /// The iteration was stopped after this many iterations without reaching
+    /// the requested accuracy.
+    IterationLimit(usize),
+    /// The ellipsoid doesn't have a positive equatorial radius and a flattening
+    /// in the range 0 .. 1.
+    InvalidEllipsoid,
+    /// A rhumb line that would go beyond a pole, where it ends.
+    BeyondPole,
+    /// A projection with a scale that isn't positive or a zone outside of
+    /// those defined, such as a UTM zone outside 1 .. 60.
+    InvalidProjection,
+    /// A spacing of points along a geodesic giving more than this many points.
+    TooManyPoints(usize),
+}
+
+impl fmt::Display for GeodesyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GeodesyError::LatitudeOutOfRange(lat) => {
+                write!(f, "Latitude of {} is outside -90° .. 90° range", lat)
+            }
+            GeodesyError::Antipodal => write!(f, "Nearly antipodal points failed to converge"),
+            GeodesyError::IterationLimit(n) => {
+                write!(f, "Failed to converge after {} iterations", n)
+            }
+            GeodesyError::InvalidEllipsoid => write!(f, "Invalid ellipsoid"),
+            GeodesyError::BeyondPole => write!(f, "Rhumb line goes beyond a pole"),
+            GeodesyError::InvalidProjection => write!(f, "Invalid projection or zone"),
+            GeodesyError::TooManyPoints(n) => write!(f, "Spacing gives more than {} points", n),
+        }
+    }
+}
+
+impl std::error::Error for GeodesyError {}
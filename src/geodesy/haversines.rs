@@ -1,4 +1,4 @@
-use crate::earth::sphere::EARTH_RADIUS;
+use crate::earth::sphere::{Sphere, FAI_SPHERE};
 use crate::geodesy::latlng::*;
 use crate::geodesy::problems::*;
 use crate::units::convert::*;
@@ -19,15 +19,21 @@ fn a_of_haversine(x: &LatLng, y: &LatLng) -> Rad {
     Rad(h_lat_f + x.lat.0.cos() * y.lat.0.cos() * h_lng_f)
 }
 
+/// The distance between two points on the FAI sphere.
 pub fn distance(x: &LatLng, y: &LatLng) -> Dist {
-    let r = EARTH_RADIUS.0 .0;
+    distance_on(&FAI_SPHERE, x, y)
+}
+
+/// The distance between two points on the given sphere.
+pub fn distance_on(sphere: &Sphere, x: &LatLng, y: &LatLng) -> Dist {
+    let Radius(Meter(r)) = sphere.radius;
     let d = 2.0 * (a_of_haversine(x, y).0.sqrt().asin());
     Dist { dist: d * r }
 }
 
 fn azimuth_fwd_aux(xll: &LatLng, yll: &LatLng) -> Rad {
     let delta_lng = yll.lng.0 - xll.lng.0;
-    let x = (delta_lng * yll.lat.0.cos()).sin();
+    let x = delta_lng.sin() * yll.lat.0.cos();
     let y = xll.lat.0.cos() * yll.lat.0.sin() - xll.lat.0.sin() * yll.lat.0.cos() * delta_lng.cos();
     Rad(x.atan2(y))
 }
@@ -46,52 +52,242 @@ fn azimuth_rev(x: &LatLng, y: &LatLng) -> Option<Rad> {
     azimuth_fwd(y, x).map(|az| az.rotate(Rad(PI)))
 }
 
-#[allow(dead_code)]
-fn direct(prob: DirectProblem) -> DirectSolution {
+// The arc σ₁₂ of the great circle leaving latitude φ₁ at azimuth α₁ on a
+// sphere of radius R, with the reduced length m₁₂ = R sin σ₁₂ and the geodesic
+// scales M₁₂ = M₂₁ = cos σ₁₂.
+fn great_circle_aux(earth_r: f64, lat1: f64, az1: f64, sigma12: f64) -> GeodesicAux {
+    let (sin_lat1, cos_lat1) = lat1.sin_cos();
+    let (sin_az1, cos_az1) = az1.sin_cos();
+    let alpha0 = (sin_az1 * cos_lat1).atan2(cos_az1.hypot(sin_az1 * sin_lat1));
+    let (sin_d, cos_d) = sigma12.sin_cos();
+
+    GeodesicAux {
+        sigma12: Rad(sigma12),
+        alpha0: Az {
+            az: Rad(alpha0).normalize().0,
+        },
+        m12: Dist {
+            dist: earth_r * sin_d,
+        },
+        scale12: cos_d,
+        scale21: cos_d,
+    }
+}
+
+/// The solution to the direct problem on the FAI sphere.
+///
+/// ```
+/// # use auxillary_sphere::geodesy::{haversines::*, latlng::LatLng, problems::*};
+/// let x = LatLng::new(51.5007, -0.1246);
+/// let y = LatLng::new(40.6892, -74.0445);
+/// let InverseSolution { s, az1, .. } = inverse(&InverseProblem { x, y });
+///
+/// let DirectSolution { y: y2, .. } = direct(&DirectProblem { x, az1, s });
+/// assert!(distance(&y, &y2).dist < 0.001);
+/// ```
+pub fn direct(prob: &DirectProblem) -> DirectSolution {
+    direct_on(&FAI_SPHERE, prob)
+}
+
+/// The solution to the direct problem on the given sphere. The longitude
+/// reached is normalized to -180° .. 180° and, as with Vincenty's α₂, the
+/// azimuth there, normalized to 0° .. 360°, is in the direction of travel.
+pub fn direct_on(sphere: &Sphere, prob: &DirectProblem) -> DirectSolution {
+    let Radius(Meter(earth_r)) = sphere.radius;
     let LatLng {
         lat: Rad(lat1),
         lng: Rad(lng1),
     } = prob.x;
     let az1 = prob.az1.az;
-    let Radius(Meter(earth_r)) = EARTH_RADIUS;
-    let d = prob.s.dist;
-    let d_r = d / earth_r;
+    let d_r = prob.s.dist / earth_r;
+
+    let (sin_lat1, cos_lat1) = lat1.sin_cos();
+    let (sin_az1, cos_az1) = az1.sin_cos();
+    let (sin_d, cos_d) = d_r.sin_cos();
 
     // SEE: https://www.movable-type.co.uk/scripts/latlong.html
-    let lat2 = (lat1.sin() * d_r.cos() + lat1.cos() * d_r.sin() * az1.cos()).asin();
-    let lng2 =
-        lng1 + (az1.sin() * d_r.sin() * lat1.cos()).atan2(d_r.cos() - lat1.sin() * lat2.sin());
-    let y: LatLng = LatLng {
-        lat: Rad(lat2),
-        lng: Rad(lng2),
-    };
-
-    let az2 = (azimuth_fwd(
-        &y,
-        &LatLng {
-            lat: Rad(lat1),
-            lng: Rad(lng1),
-        },
-    ))
-    .map(|az| -> Az {
-        let deg: Deg = rad_to_deg(az.rotate(Rad(PI)));
-        Az {
-            az: deg_to_rad(deg.normalize()).0,
-        }
-    });
+    let lat2 = (sin_lat1 * cos_d + cos_lat1 * sin_d * cos_az1).asin();
+    let lng2 = lng1 + (sin_az1 * sin_d * cos_lat1).atan2(cos_d - sin_lat1 * lat2.sin());
+
+    // From Napier's rules for the spherical triangle with the pole.
+    let az2 = (sin_az1 * cos_lat1).atan2(cos_d * cos_lat1 * cos_az1 - sin_lat1 * sin_d);
 
     DirectSolution {
         y: LatLng {
             lat: Rad(lat2),
-            lng: Rad(lng2),
+            lng: plus_minus_pi_rad(Rad(lng2)),
         },
+        az2: Some(Az {
+            az: Rad(az2).normalize().0,
+        }),
+        iterations: 0,
+        aux: Some(great_circle_aux(earth_r, lat1, az1, d_r)),
+    }
+}
+
+/// The solution to the inverse problem on the FAI sphere.
+pub fn inverse(prob: &InverseProblem) -> InverseSolution {
+    inverse_on(&FAI_SPHERE, prob)
+}
+
+/// The solution to the inverse problem on the given sphere, with both azimuths
+/// normalized to 0° .. 360° as with the solvers on the ellipsoid.
+pub fn inverse_on(sphere: &Sphere, InverseProblem { x, y }: &InverseProblem) -> InverseSolution {
+    let az1 = azimuth_fwd(x, y)
+        .map(|az| Az {
+            az: az.normalize().0,
+        })
+        .unwrap();
+    let az2 = azimuth_rev(x, y).map(|az| Az {
+        az: az.normalize().0,
+    });
+    let s = distance_on(sphere, x, y);
+    let Radius(Meter(earth_r)) = sphere.radius;
+    InverseSolution {
+        s,
+        az1,
         az2,
+        iterations: 0,
+        aux: Some(great_circle_aux(earth_r, x.lat.0, az1.az, s.dist / earth_r)),
     }
 }
 
-pub fn inverse(InverseProblem { x, y }: &InverseProblem) -> InverseSolution {
-    let az1 = azimuth_fwd(x, y).map(|az| Az { az: az.0 }).unwrap();
-    let az2 = azimuth_rev(x, y).map(|az| Az { az: az.0 });
-    let s = distance(x, y);
-    InverseSolution { s, az1, az2 }
+/// The cross-track and along-track distances of a point from the great circle
+/// through two others on the FAI sphere.
+pub fn track(prob: &TrackProblem) -> TrackSolution {
+    track_on(&FAI_SPHERE, prob)
+}
+
+/// The cross-track and along-track distances of a point from the great circle
+/// through two others on the given sphere.
+///
+/// ```
+/// # use auxillary_sphere::{
+/// #     earth::sphere::FAI_SPHERE,
+/// #     geodesy::{haversines::*, latlng::LatLng, problems::*},
+/// # };
+/// // Along the equator, a point north of it is to the left of an eastward track.
+/// let prob = TrackProblem {
+///     x: LatLng::new(0.0, 0.0),
+///     y: LatLng::new(0.0, 10.0),
+///     p: LatLng::new(1.0, 5.0),
+/// };
+/// let TrackSolution { cross_track, closest, .. } = track_on(&FAI_SPHERE, &prob);
+///
+/// assert!(cross_track.dist < 0.0);
+/// assert!(distance(&closest, &LatLng::new(0.0, 5.0)).dist < 1e-6);
+/// ```
+pub fn track_on(sphere: &Sphere, prob: &TrackProblem) -> TrackSolution {
+    let Radius(Meter(r)) = sphere.radius;
+    let TrackProblem { x, y, p } = prob;
+
+    // SEE: https://www.movable-type.co.uk/scripts/latlong.html#cross-track
+    let d13 = distance_on(sphere, x, p).dist / r;
+    let Rad(az12) = azimuth_fwd_aux(x, y);
+    let Rad(az13) = azimuth_fwd_aux(x, p);
+    let (sin_d13, cos_d13) = d13.sin_cos();
+    let (sin_daz, cos_daz) = (az13 - az12).sin_cos();
+
+    let xt = (sin_d13 * sin_daz).asin();
+
+    // From Napier's rules for the right spherical triangle at the closest point,
+    // tan at = tan δ₁₃ cos(θ₁₃ - θ₁₂).
+    let at = (sin_d13 * cos_daz).atan2(cos_d13);
+
+    let DirectSolution { y: closest, .. } = direct_on(
+        sphere,
+        &DirectProblem {
+            x: *x,
+            az1: Az { az: az12 },
+            s: Dist { dist: at * r },
+        },
+    );
+
+    TrackSolution {
+        cross_track: Dist { dist: xt * r },
+        along_track: Dist { dist: at * r },
+        closest,
+        iterations: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{earth::ellipsoid::Ellipsoid, geodesy::karney::Geodesic};
+
+    #[test]
+    fn reduced_length_on_sphere() {
+        // On a sphere m₁₂ = R sin σ₁₂ and M₁₂ = M₂₁ = cos σ₁₂ with σ₁₂ = s₁₂ / R,
+        // as from Karney's series on an ellipsoid with no flattening. The arc
+        // is longer than a quarter circle so the scales are negative.
+        let Radius(Meter(r)) = FAI_SPHERE.radius;
+        let geodesic = Geodesic::new(&Ellipsoid {
+            equatorial_r: FAI_SPHERE.radius,
+            recip_f: f64::INFINITY,
+        })
+        .unwrap();
+        let prob = InverseProblem {
+            x: LatLng::new(-35.0, 20.0),
+            y: LatLng::new(50.0, 160.0),
+        };
+        let inv = inverse_on(&FAI_SPHERE, &prob);
+        let fwd = direct_on(
+            &FAI_SPHERE,
+            &DirectProblem {
+                x: prob.x,
+                az1: inv.az1,
+                s: inv.s,
+            },
+        );
+        let karney = geodesic.inverse(&prob).unwrap().aux.unwrap();
+
+        let sigma12 = inv.s.dist / r;
+        assert!(sigma12.cos() < 0.0);
+        for aux in [inv.aux.unwrap(), fwd.aux.unwrap()] {
+            assert!((aux.m12.dist - r * sigma12.sin()).abs() < 1e-6);
+            assert!((aux.scale12 - sigma12.cos()).abs() < 1e-12);
+            assert!((aux.scale21 - sigma12.cos()).abs() < 1e-12);
+            assert!((aux.m12.dist - karney.m12.dist).abs() < 1e-6);
+            assert!((aux.scale12 - karney.scale12).abs() < 1e-12);
+            assert!((aux.alpha0.az - karney.alpha0.az).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn lands_end_to_john_o_groats() {
+        // The initial bearing of 009°07′11″ and final bearing of 011°16′31″
+        // published by Movable Type.
+        // SEE: https://www.movable-type.co.uk/scripts/latlong.html
+        let x = LatLng::from((
+            DMS {
+                deg: 50,
+                min: 3,
+                sec: 59.0,
+            },
+            DMS {
+                deg: -5,
+                min: 42,
+                sec: 53.0,
+            },
+        ));
+        let y = LatLng::from((
+            DMS {
+                deg: 58,
+                min: 38,
+                sec: 38.0,
+            },
+            DMS {
+                deg: -3,
+                min: 4,
+                sec: 12.0,
+            },
+        ));
+        let InverseSolution { az1, az2, .. } = inverse(&InverseProblem { x, y });
+        let Deg(az1) = rad_to_deg(Rad(az1.az));
+        let Deg(az2) = rad_to_deg(Rad(az2.unwrap().az));
+
+        assert!((az1 - (9.0 + 7.0 / 60.0 + 11.0 / 3600.0)).abs() < 1.0 / 3600.0);
+        assert!((az2 - (11.0 + 16.0 / 60.0 + 31.0 / 3600.0)).abs() < 1.0 / 3600.0);
+    }
 }
@@ -0,0 +1,395 @@
+use crate::units::{Meter, Radius};
+use std::f64::consts::FRAC_PI_2;
+
+use super::{
+    error::GeodesyError,
+    latlng::LatLng,
+    problems::{
+        Az, Course, Crossing, DirectProblem, DirectSolution, Dist, Intersection,
+        IntersectionProblem, InverseProblem, InverseSolution,
+    },
+    solver::GeodesicSolver,
+};
+
+// The intersection of two geodesics, moving a point along each towards where
+// they cross, found as the intersection of great circles on a sphere through
+// the two points with the azimuths of the geodesics there, until the moves are
+// less than a micrometre.
+//
+// SEE: Baselga, S., Martínez-Llario, J.C., Intersection and point-to-line
+// solutions for geodesics on the ellipsoid, Stud Geophys Geod 62, 353–363
+// (2018), <https://doi.org/10.1007/s11200-017-1020-z>
+
+const TOLERANCE: f64 = 1e-6;
+const MAX_ITERATIONS: usize = 50;
+
+// Below this sine of the angle between the planes of the great circles, where
+// they cross is too ill-conditioned to find and the courses are taken to be
+// parallel, or the same great circle when the second point is also within
+// COINCIDENT, in radians, of the great circle of the first.
+const PARALLEL: f64 = 1e-9;
+const COINCIDENT: f64 = 1e-12;
+
+type Vec3 = [f64; 3];
+
+fn dot(u: Vec3, v: Vec3) -> f64 {
+    u[0] * v[0] + u[1] * v[1] + u[2] * v[2]
+}
+
+fn cross(u: Vec3, v: Vec3) -> Vec3 {
+    [
+        u[1] * v[2] - u[2] * v[1],
+        u[2] * v[0] - u[0] * v[2],
+        u[0] * v[1] - u[1] * v[0],
+    ]
+}
+
+// A point on the equator of the unit sphere and the direction of an azimuth
+// there.
+fn on_equator(lng: f64, az: f64) -> (Vec3, Vec3) {
+    let (sin_lng, cos_lng) = lng.sin_cos();
+    let (sin_az, cos_az) = az.sin_cos();
+    let p = [cos_lng, sin_lng, 0.0];
+    let t = [-sin_lng * sin_az, cos_lng * sin_az, cos_az];
+    (p, t)
+}
+
+// The start and azimuth of a course.
+fn heading<S: GeodesicSolver + ?Sized>(
+    solver: &S,
+    course: &Course,
+) -> Result<(LatLng, Az), GeodesyError> {
+    match *course {
+        Course::Heading { x, az } => Ok((x, az)),
+        Course::Through { x, y } => Ok((x, solver.inverse(&InverseProblem { x, y })?.az1)),
+    }
+}
+
+// The point and azimuth a distance along a course.
+fn along<S: GeodesicSolver + ?Sized>(
+    solver: &S,
+    (x, az1): (LatLng, Az),
+    dist: f64,
+) -> Result<(LatLng, f64), GeodesyError> {
+    let DirectSolution { y, az2, .. } = solver.direct(&DirectProblem {
+        x,
+        az1,
+        s: Dist { dist },
+    })?;
+    Ok((y, az2.map_or(az1.az, |az| az.az)))
+}
+
+enum Step {
+    Moves(f64, f64),
+    Parallel,
+    Coincident,
+}
+
+// The moves along each course, in radians of the sphere, towards a crossing
+// from points a distance c apart, the courses making angles with the geodesic
+// between the points of da at the first and db at the second. With near, the
+// crossing nearer to the points, otherwise the one further away.
+fn step(c: f64, da: f64, db: f64, near: bool) -> Step {
+    // Along the equator from a at 0° to b at c, the azimuth of a to b is 90°.
+    let (pa, ta) = on_equator(0.0, FRAC_PI_2 + da);
+    let (pb, tb) = on_equator(c, FRAC_PI_2 + db);
+    let na = cross(pa, ta);
+    let nb = cross(pb, tb);
+    let x = cross(na, nb);
+    let sin_x = dot(x, x).sqrt();
+
+    if sin_x < PARALLEL {
+        return if dot(pb, na).abs() < COINCIDENT {
+            Step::Coincident
+        } else {
+            Step::Parallel
+        };
+    }
+
+    let moves = |sign: f64| {
+        let x = x.map(|xi| sign * xi / sin_x);
+        let sa = dot(x, ta).atan2(dot(x, pa));
+        let sb = dot(x, tb).atan2(dot(x, pb));
+        (sa, sb)
+    };
+    let (plus, minus) = (moves(1.0), moves(-1.0));
+    let nearer =
+        |(sa, sb): (f64, f64), (ta, tb): (f64, f64)| sa.abs() + sb.abs() <= ta.abs() + tb.abs();
+    let (sa, sb) = match (nearer(plus, minus), near) {
+        (true, true) | (false, false) => plus,
+        _ => minus,
+    };
+
+    Step::Moves(sa, sb)
+}
+
+enum Found {
+    Crossing(Crossing),
+    Parallel,
+    Coincident,
+}
+
+// The crossing nearest to the start of the iteration, or on the first step the
+// further one when not near, unless a step finds the courses parallel or
+// coincident.
+fn crossing<S: GeodesicSolver + ?Sized>(
+    solver: &S,
+    a: (LatLng, Az),
+    b: (LatLng, Az),
+    near: bool,
+) -> Result<Found, GeodesyError> {
+    let Radius(Meter(r)) = solver.radius();
+    let (mut sa, mut sb) = (0.0, 0.0);
+
+    for i in 1..=MAX_ITERATIONS {
+        let (ya, az_a) = along(solver, a, sa)?;
+        let (yb, az_b) = along(solver, b, sb)?;
+        let InverseSolution { s, az1, az2, .. } =
+            solver.inverse(&InverseProblem { x: ya, y: yb })?;
+        let az2 = az2.map_or(az1.az, |az| az.az);
+        let near = near || i > 1;
+        let crossing = Crossing {
+            y: ya,
+            sa: Dist { dist: sa },
+            sb: Dist { dist: sb },
+        };
+
+        // With the points together the courses cross there, where their
+        // azimuths may be ill-defined as at a pole. Only when looking for the
+        // further crossing from a common start do they need to move apart.
+        let (c, da, db) = if s.dist < TOLERANCE {
+            if near {
+                return Ok(Found::Crossing(crossing));
+            }
+            (0.0, 0.0, az_b - az_a)
+        } else {
+            (s.dist / r, az_a - az1.az, az_b - az2)
+        };
+
+        let (ma, mb) = match step(c, da, db, near) {
+            Step::Moves(ma, mb) => (ma * r, mb * r),
+            Step::Parallel => return Ok(Found::Parallel),
+            Step::Coincident => return Ok(Found::Coincident),
+        };
+
+        if ma.abs() < TOLERANCE && mb.abs() < TOLERANCE {
+            return Ok(Found::Crossing(crossing));
+        }
+
+        sa += ma;
+        sb += mb;
+    }
+
+    Err(GeodesyError::IterationLimit(MAX_ITERATIONS))
+}
+
+/// Where two geodesics cross, with any solver.
+pub fn intersection<S: GeodesicSolver + ?Sized>(
+    solver: &S,
+    prob: &IntersectionProblem,
+) -> Result<Intersection, GeodesyError> {
+    let a = heading(solver, &prob.a)?;
+    let b = heading(solver, &prob.b)?;
+
+    // Whether the courses start on the same great circle of the sphere.
+    let InverseSolution { s, az1, az2, .. } = solver.inverse(&InverseProblem { x: a.0, y: b.0 })?;
+    let Radius(Meter(r)) = solver.radius();
+    let first = if s.dist < TOLERANCE {
+        step(0.0, 0.0, b.1.az - a.1.az, true)
+    } else {
+        let az2 = az2.map_or(az1.az, |az| az.az);
+        step(s.dist / r, a.1.az - az1.az, b.1.az - az2, true)
+    };
+
+    match first {
+        Step::Parallel => Ok(Intersection::Parallel),
+        Step::Coincident => Ok(Intersection::Coincident),
+        // The near crossing is kept whatever the search for the far one finds,
+        // even an error.
+        Step::Moves(..) => match crossing(solver, a, b, true)? {
+            Found::Crossing(near) => match crossing(solver, a, b, false) {
+                Ok(Found::Crossing(far)) => Ok(Intersection::Crossings([near, far])),
+                _ => Ok(Intersection::Crossing(near)),
+            },
+            Found::Parallel => Ok(Intersection::Parallel),
+            Found::Coincident => Ok(Intersection::Coincident),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        earth::{ellipsoid::WGS84, sphere::FAI_SPHERE},
+        geodesy::{
+            karney::Geodesic,
+            problems::TrackProblem,
+            solver::{EllipsoidSolver, SphereSolver},
+        },
+    };
+
+    fn equator_and_meridian() -> IntersectionProblem {
+        IntersectionProblem {
+            a: Course::Through {
+                x: LatLng::new(0.0, 0.0),
+                y: LatLng::new(0.0, 10.0),
+            },
+            b: Course::Through {
+                x: LatLng::new(-1.0, 5.0),
+                y: LatLng::new(1.0, 5.0),
+            },
+        }
+    }
+
+    #[test]
+    fn crossings() {
+        let geodesic = Geodesic::new(&WGS84).unwrap();
+        let sphere = SphereSolver::new(FAI_SPHERE);
+        let prob = equator_and_meridian();
+
+        for solver in [&geodesic as &dyn GeodesicSolver, &sphere] {
+            let Intersection::Crossings([near, far]) = intersection(solver, &prob).unwrap() else {
+                panic!("expected the courses to cross");
+            };
+            for (crossing, y) in [
+                (near, LatLng::new(0.0, 5.0)),
+                (far, LatLng::new(0.0, -175.0)),
+            ] {
+                let miss = geodesic
+                    .inverse(&InverseProblem { x: crossing.y, y })
+                    .unwrap();
+                assert!(miss.s.dist < 1e-6);
+            }
+        }
+
+        // Along the equator, 5° of longitude.
+        let Intersection::Crossings([near, _]) = intersection(&geodesic, &prob).unwrap() else {
+            panic!("expected the courses to cross");
+        };
+        let Radius(Meter(a)) = WGS84.equatorial_r;
+        assert!((near.sa.dist - a * 5f64.to_radians()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn oblique() {
+        // Lines across western Europe, each crossing the other between its
+        // ends, at an angle other than a right angle.
+        let a = (LatLng::new(51.0, -1.0), LatLng::new(45.0, 10.0));
+        let b = (LatLng::new(40.0, 0.0), LatLng::new(55.0, 8.0));
+        let prob = IntersectionProblem {
+            a: Course::Through { x: a.0, y: a.1 },
+            b: Course::Through { x: b.0, y: b.1 },
+        };
+        let geodesic = Geodesic::new(&WGS84).unwrap();
+        let ellipsoid = EllipsoidSolver::new(WGS84);
+
+        for solver in [&geodesic as &dyn GeodesicSolver, &ellipsoid] {
+            let near = match intersection(solver, &prob).unwrap() {
+                Intersection::Crossings([near, _]) | Intersection::Crossing(near) => near,
+                _ => panic!("expected the courses to cross"),
+            };
+
+            // On both geodesics, between the ends of each.
+            for ((x, y), s) in [(a, near.sa), (b, near.sb)] {
+                let track = solver.track(&TrackProblem { x, y, p: near.y }).unwrap();
+                let span = solver.inverse(&InverseProblem { x, y }).unwrap().s;
+
+                assert!(track.cross_track.dist.abs() < 1e-6);
+                assert!((track.along_track.dist - s.dist).abs() < 1e-6);
+                assert!(s.dist > 0.0 && s.dist < span.dist);
+            }
+        }
+    }
+
+    // Solves as Karney's method but, as if failing to converge, only to within
+    // a quarter of the way round the earth.
+    struct Nearby(Geodesic);
+
+    impl GeodesicSolver for Nearby {
+        fn direct(&self, prob: &DirectProblem) -> Result<DirectSolution, GeodesyError> {
+            if prob.s.dist.abs() > 10000000.0 {
+                Err(GeodesyError::IterationLimit(0))
+            } else {
+                self.0.direct(prob)
+            }
+        }
+
+        fn inverse(&self, prob: &InverseProblem) -> Result<InverseSolution, GeodesyError> {
+            self.0.inverse(prob)
+        }
+
+        fn radius(&self) -> Radius {
+            self.0.radius()
+        }
+    }
+
+    #[test]
+    fn far_crossing_not_found() {
+        let solver = Nearby(Geodesic::new(&WGS84).unwrap());
+        let prob = equator_and_meridian();
+
+        let Intersection::Crossing(near) = intersection(&solver, &prob).unwrap() else {
+            panic!("expected only the near crossing");
+        };
+        let miss = solver
+            .inverse(&InverseProblem {
+                x: near.y,
+                y: LatLng::new(0.0, 5.0),
+            })
+            .unwrap();
+        assert!(miss.s.dist < 1e-6);
+    }
+
+    #[test]
+    fn parallel() {
+        // Heading east along the equator and from a millimetre north of it,
+        // crossing where that can't be found a quarter of the way round.
+        let east = Az { az: FRAC_PI_2 };
+        let prob = IntersectionProblem {
+            a: Course::Heading {
+                x: LatLng::new(0.0, 0.0),
+                az: east,
+            },
+            b: Course::Heading {
+                x: LatLng::new(1e-8, 10.0),
+                az: east,
+            },
+        };
+        let geodesic = Geodesic::new(&WGS84).unwrap();
+        let sphere = SphereSolver::new(FAI_SPHERE);
+
+        for solver in [&geodesic as &dyn GeodesicSolver, &sphere] {
+            assert!(matches!(
+                intersection(solver, &prob).unwrap(),
+                Intersection::Parallel
+            ));
+        }
+    }
+
+    #[test]
+    fn coincident() {
+        let geodesic = Geodesic::new(&WGS84).unwrap();
+        let prob = IntersectionProblem {
+            a: Course::Through {
+                x: LatLng::new(10.0, 20.0),
+                y: LatLng::new(30.0, 40.0),
+            },
+            b: Course::Heading {
+                x: LatLng::new(10.0, 20.0),
+                az: geodesic
+                    .inverse(&InverseProblem {
+                        x: LatLng::new(10.0, 20.0),
+                        y: LatLng::new(30.0, 40.0),
+                    })
+                    .unwrap()
+                    .az1,
+            },
+        };
+
+        assert!(matches!(
+            intersection(&geodesic, &prob).unwrap(),
+            Intersection::Coincident
+        ));
+    }
+}
@@ -0,0 +1,1363 @@
+use std::f64::consts::{FRAC_1_SQRT_2, PI};
+
+use crate::{
+    earth::ellipsoid::{flattening, polar_r, Ellipsoid},
+    units::{
+        convert::{deg_to_rad, rad_to_deg},
+        Angle, Deg, Meter, Rad, Radius,
+    },
+};
+
+use super::{
+    error::GeodesyError,
+    latlng::LatLng,
+    problems::{
+        Az, DirectProblem, DirectSolution, Dist, GeodesicAux, InverseProblem, InverseSolution,
+    },
+    vincenty::check_problem,
+};
+
+// Solutions of the direct and inverse geodesy problems from ...
+//
+// Algorithms for geodesics
+// Journal of Geodesy 87, 43–55 (2013)
+// C. F. F. Karney, <https://doi.org/10.1007/s00190-012-0578-z>
+//
+// This follows the series expansions to 6th order of GeographicLib's
+// geodesic.c, converging for all pairs of points and accurate to within 15
+// nanometres for the WGS84 ellipsoid.
+//
+// Symbol reference from Karney's paper.
+// a, b   = equatorial and polar semiaxes of the ellipsoid
+// f      = flattening (a - b) / a
+// n      = third flattening (a - b) / (a + b)
+// e², e'² = first and second eccentricity squared
+// φ      = geodetic latitude
+// β      = reduced latitude, defined by tan β = (1 - f) tan φ
+// λ      = longitude on the ellipsoid
+// ω      = longitude on the auxiliary sphere
+// α      = azimuth of the geodesic, α₀ where it crosses the equator northwards
+// σ      = arc length on the auxiliary sphere, from the equator crossing
+// s      = length of the geodesic
+// k², ε  = k² = e'² cos² α₀ and ε = (√(1 + k²) - 1) / (√(1 + k²) + 1)
+// m₁₂    = reduced length of the geodesic
+// M₁₂    = geodesic scale, M₂₁ for the geodesic traversed in reverse
+// S₁₂    = area between the geodesic and the equator
+// c²     = authalic radius squared
+
+pub(crate) const ORDER: usize = 6;
+const N_A3X: usize = ORDER;
+const N_C3X: usize = (ORDER * (ORDER - 1)) / 2;
+const N_C4X: usize = (ORDER * (ORDER + 1)) / 2;
+
+const MAXIT1: usize = 20;
+const MAXIT2: usize = MAXIT1 + f64::MANTISSA_DIGITS as usize + 10;
+
+const TOL0: f64 = f64::EPSILON;
+const TOL1: f64 = 200.0 * TOL0;
+const XTHRESH: f64 = 1000.0 * TOL2;
+const TOL2: f64 = 1.4901161193847656e-8; // √ε
+const TOLB: f64 = TOL0 * TOL2;
+const TINY: f64 = 1.4916681462400413e-154; // √f64::MIN_POSITIVE
+
+fn sq(x: f64) -> f64 {
+    x * x
+}
+
+// The sum of u and v with the roundoff error of the sum.
+pub(crate) fn sum_error(u: f64, v: f64) -> (f64, f64) {
+    let s = u + v;
+    let up = s - v;
+    let vpp = s - up;
+    (s, -((up - u) + (vpp - v)))
+}
+
+// Evaluates the polynomial with coefficients, highest power first, at x.
+fn polyval(p: &[f64], x: f64) -> f64 {
+    p.iter().fold(0.0, |y, c| y * x + c)
+}
+
+// Rounds very small angles so that tiny differences from zero don't become
+// significant.
+fn ang_round(x: f64) -> f64 {
+    let z = 1.0 / 16.0;
+    let y = x.abs();
+    let w = z - y;
+    (if w > 0.0 { z - w } else { y }).copysign(x)
+}
+
+fn norm2(s: f64, c: f64) -> (f64, f64) {
+    let r = s.hypot(c);
+    (s / r, c / r)
+}
+
+// Normalizes degrees to -180° .. 180°.
+pub(crate) fn ang_normalize(x: f64) -> f64 {
+    let x = x % 360.0;
+    if x <= -180.0 {
+        x + 360.0
+    } else if x <= 180.0 {
+        x
+    } else {
+        x - 360.0
+    }
+}
+
+// The difference y - x in degrees, reduced to -180° .. 180°, with its roundoff
+// error.
+pub(crate) fn ang_diff(x: f64, y: f64) -> (f64, f64) {
+    let (d, t) = sum_error(ang_normalize(-x), ang_normalize(y));
+    let d = ang_normalize(d);
+    sum_error(if d == 180.0 && t > 0.0 { -180.0 } else { d }, t)
+}
+
+// The sine and cosine of degrees, exact for multiples of 90°.
+fn sincosd(x: f64) -> (f64, f64) {
+    let r = x % 360.0;
+    let q = (r / 90.0).round();
+    let r = (r - 90.0 * q).to_radians();
+    let (s, c) = r.sin_cos();
+    let (sinx, cosx) = match (q as i64) & 3 {
+        0 => (s, c),
+        1 => (c, -s),
+        2 => (-s, -c),
+        _ => (-c, s),
+    };
+    (
+        if sinx == 0.0 { sinx.copysign(x) } else { sinx },
+        cosx + 0.0,
+    )
+}
+
+// The angle in degrees of atan2(y, x), exact for multiples of 90°.
+fn atan2d(y: f64, x: f64) -> f64 {
+    let (y, x, q) = if y.abs() > x.abs() {
+        (x, y, 2)
+    } else {
+        (y, x, 0)
+    };
+    let (x, q) = if x.is_sign_negative() {
+        (-x, q + 1)
+    } else {
+        (x, q)
+    };
+    let ang = y.atan2(x).to_degrees();
+    match q {
+        1 => 180.0_f64.copysign(y) - ang,
+        2 => 90.0 - ang,
+        3 => -90.0 + ang,
+        _ => ang,
+    }
+}
+
+// Evaluates Σ c[l] sin 2lx, for l = 1 .. n, when sinp, or Σ c[l] cos (2l + 1)x,
+// for l = 0 .. n - 1, otherwise, with Clenshaw summation.
+pub(crate) fn sin_cos_series(sinp: bool, sinx: f64, cosx: f64, c: &[f64]) -> f64 {
+    let offset = if sinp { 1 } else { 0 };
+    let n = c.len() - offset;
+    let mut k = c.len();
+    let ar = 2.0 * (cosx - sinx) * (cosx + sinx);
+    let mut y0 = if n & 1 == 1 {
+        k -= 1;
+        c[k]
+    } else {
+        0.0
+    };
+    let mut y1 = 0.0;
+    for _ in 0..n / 2 {
+        k -= 1;
+        y1 = ar * y0 - y1 + c[k];
+        k -= 1;
+        y0 = ar * y1 - y0 + c[k];
+    }
+
+    if sinp {
+        2.0 * sinx * cosx * y0
+    } else {
+        cosx * (y0 - y1)
+    }
+}
+
+// (1 - ε) A₁ - 1
+fn a1m1f(eps: f64) -> f64 {
+    let t = polyval(&[1.0, 4.0, 64.0, 0.0], sq(eps)) / 256.0;
+    (t + eps) / (1.0 - eps)
+}
+
+// C₁ₗ, at c[l] for l = 1 .. 6.
+pub(crate) fn c1f(eps: f64) -> [f64; ORDER + 1] {
+    const COEFF: [&[f64]; ORDER] = [
+        &[-1.0, 6.0, -16.0, 32.0],
+        &[-9.0, 64.0, -128.0, 2048.0],
+        &[9.0, -16.0, 768.0],
+        &[3.0, -5.0, 512.0],
+        &[-7.0, 1280.0],
+        &[-7.0, 2048.0],
+    ];
+    series_in_eps2(&COEFF, eps)
+}
+
+// C'₁ₗ, at c[l] for l = 1 .. 6.
+pub(crate) fn c1pf(eps: f64) -> [f64; ORDER + 1] {
+    const COEFF: [&[f64]; ORDER] = [
+        &[205.0, -432.0, 768.0, 1536.0],
+        &[4005.0, -4736.0, 3840.0, 12288.0],
+        &[-225.0, 116.0, 384.0],
+        &[-7173.0, 2695.0, 7680.0],
+        &[3467.0, 7680.0],
+        &[38081.0, 61440.0],
+    ];
+    series_in_eps2(&COEFF, eps)
+}
+
+// (1 + ε) A₂ - 1
+fn a2m1f(eps: f64) -> f64 {
+    let t = polyval(&[-11.0, -28.0, -192.0, 0.0], sq(eps)) / 256.0;
+    (t - eps) / (1.0 + eps)
+}
+
+// C₂ₗ, at c[l] for l = 1 .. 6.
+fn c2f(eps: f64) -> [f64; ORDER + 1] {
+    const COEFF: [&[f64]; ORDER] = [
+        &[1.0, 2.0, 16.0, 32.0],
+        &[35.0, 64.0, 384.0, 2048.0],
+        &[15.0, 80.0, 768.0],
+        &[7.0, 35.0, 512.0],
+        &[63.0, 1280.0],
+        &[77.0, 2048.0],
+    ];
+    series_in_eps2(&COEFF, eps)
+}
+
+// Each of the coefficients is εˡ times a polynomial in ε², the last element of
+// each being the divisor.
+fn series_in_eps2(coeff: &[&[f64]; ORDER], eps: f64) -> [f64; ORDER + 1] {
+    let eps2 = sq(eps);
+    let mut c = [0.0; ORDER + 1];
+    let mut d = eps;
+    for (l, p) in coeff.iter().enumerate() {
+        let (divisor, p) = p.split_last().unwrap();
+        c[l + 1] = d * polyval(p, eps2) / divisor;
+        d *= eps;
+    }
+    c
+}
+
+// The coefficients of a series in ε, each a polynomial in n, the last element
+// of each being the divisor.
+fn coeffs_in_n(coeff: &[&[f64]], n: f64) -> Vec<f64> {
+    coeff
+        .iter()
+        .map(|p| {
+            let (divisor, p) = p.split_last().unwrap();
+            polyval(p, n) / divisor
+        })
+        .collect()
+}
+
+// Solves the astroid problem, k⁴ + 2k³ - (x² + y² - 1)k² - 2y²k - y² = 0, for
+// the positive root k.
+fn astroid(x: f64, y: f64) -> f64 {
+    let p = sq(x);
+    let q = sq(y);
+    let r = (p + q - 1.0) / 6.0;
+    if q == 0.0 && r <= 0.0 {
+        return 0.0;
+    }
+
+    let s = p * q / 4.0;
+    let r2 = sq(r);
+    let r3 = r * r2;
+    let disc = s * (s + 2.0 * r3);
+    let mut u = r;
+    if disc >= 0.0 {
+        let mut t3 = s + r3;
+        t3 += if t3 < 0.0 { -disc.sqrt() } else { disc.sqrt() };
+        let t = t3.cbrt();
+        u += t + if t != 0.0 { r2 / t } else { 0.0 };
+    } else {
+        let ang = (-disc).sqrt().atan2(-(s + r3));
+        u += 2.0 * r * (ang / 3.0).cos();
+    }
+
+    let v = (sq(u) + q).sqrt();
+    let uv = if u < 0.0 { q / (v - u) } else { u + v };
+    let w = (uv - q) / (2.0 * v);
+    uv / ((uv + sq(w)).sqrt() + w)
+}
+
+// The lengths of a geodesic from the start of the σ₁₂ arc at σ₁ to its end at
+// σ₂ on the auxiliary sphere, each in units of b.
+#[derive(Debug, Clone, Copy)]
+struct Lengths {
+    s12b: f64,
+    m12b: f64,
+    j12: f64,
+}
+
+// A line short enough to solve without iteration, (sin α₂, cos α₂), σ₁₂ and
+// the mean of √(1 + e'² sin² β) over the line.
+#[derive(Debug, Clone, Copy)]
+struct ShortLine {
+    alp2: (f64, f64),
+    sig12: f64,
+    dnm: f64,
+}
+
+// The position on a geodesic and the azimuth there, all in degrees.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Position {
+    pub(crate) lat2: f64,
+    pub(crate) lng2: f64,
+    pub(crate) azi2: f64,
+    pub(crate) sig12: f64,
+}
+
+// A solution of the inverse problem with azimuths in degrees and, when asked
+// for, the area S₁₂ in square metres.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Inverse {
+    pub(crate) s12: f64,
+    pub(crate) azi1: f64,
+    pub(crate) azi2: f64,
+    pub(crate) area: Option<f64>,
+    pub(crate) iterations: usize,
+    pub(crate) sig12: f64,
+}
+
+/// The ellipsoid with its derived constants and the coefficients of the series
+/// in its third flattening, n, that don't depend on the geodesic, computed once
+/// to solve any number of geodesy problems.
+#[derive(Clone)]
+pub struct Geodesic {
+    ellipsoid: Ellipsoid,
+    a: f64,
+    f: f64,
+    f1: f64,
+    e2: f64,
+    ep2: f64,
+    n: f64,
+    b: f64,
+    c2: f64,
+    etol2: f64,
+    a3x: [f64; N_A3X],
+    c3x: [f64; N_C3X],
+    c4x: [f64; N_C4X],
+}
+
+// The geodesic from a point at an azimuth, ready to give the position at any
+// distance or arc length along it.
+#[derive(Debug, Clone)]
+pub(crate) struct Line {
+    lng1: f64,
+    b: f64,
+    f1: f64,
+    salp0: f64,
+    calp0: f64,
+    k2: f64,
+    ssig1: f64,
+    csig1: f64,
+    stau1: f64,
+    ctau1: f64,
+    somg1: f64,
+    comg1: f64,
+    a1m1: f64,
+    a3c: f64,
+    b11: f64,
+    b31: f64,
+    c1a: [f64; ORDER + 1],
+    c1pa: [f64; ORDER + 1],
+    c3a: [f64; ORDER],
+}
+
+impl Geodesic {
+    pub fn new(ellipsoid: &Ellipsoid) -> Result<Self, GeodesyError> {
+        check_problem(ellipsoid, Rad(0.0))?;
+
+        let Radius(Meter(a)) = ellipsoid.equatorial_r;
+        let f = flattening(ellipsoid);
+        let f1 = 1.0 - f;
+        let ep2 = ellipsoid.second_eccentricity_sq();
+        let n = ellipsoid.third_flattening();
+        let Radius(Meter(b)) = polar_r(ellipsoid);
+        let Radius(Meter(c)) = ellipsoid.authalic_r();
+        let etol2 = 0.1 * TOL2 / (f.abs().max(0.001) * (1.0 - f / 2.0).min(1.0) / 2.0).sqrt();
+
+        // A₃ as a polynomial in ε, highest power first.
+        const A3: [&[f64]; N_A3X] = [
+            &[-3.0, 128.0],
+            &[-2.0, -3.0, 64.0],
+            &[-1.0, -3.0, -1.0, 16.0],
+            &[3.0, -1.0, -2.0, 8.0],
+            &[1.0, -1.0, 2.0],
+            &[1.0, 1.0],
+        ];
+
+        // C₃ₗ for l = 1 .. 5, each as a polynomial in ε, highest power first.
+        const C3: [&[f64]; N_C3X] = [
+            &[3.0, 128.0],
+            &[2.0, 5.0, 128.0],
+            &[-1.0, 3.0, 3.0, 64.0],
+            &[-1.0, 0.0, 1.0, 8.0],
+            &[-1.0, 1.0, 4.0],
+            &[5.0, 256.0],
+            &[1.0, 3.0, 128.0],
+            &[-3.0, -2.0, 3.0, 64.0],
+            &[1.0, -3.0, 2.0, 32.0],
+            &[7.0, 512.0],
+            &[-10.0, 9.0, 384.0],
+            &[5.0, -9.0, 5.0, 192.0],
+            &[7.0, 512.0],
+            &[-14.0, 7.0, 512.0],
+            &[21.0, 2560.0],
+        ];
+
+        // C₄ₗ for l = 0 .. 5, each as a polynomial in ε, highest power first.
+        const C4: [&[f64]; N_C4X] = [
+            &[97.0, 15015.0],
+            &[1088.0, 156.0, 45045.0],
+            &[-224.0, -4784.0, 1573.0, 45045.0],
+            &[-10656.0, 14144.0, -4576.0, -858.0, 45045.0],
+            &[64.0, 624.0, -4576.0, 6864.0, -3003.0, 15015.0],
+            &[100.0, 208.0, 572.0, 3432.0, -12012.0, 30030.0, 45045.0],
+            &[1.0, 9009.0],
+            &[-2944.0, 468.0, 135135.0],
+            &[5792.0, 1040.0, -1287.0, 135135.0],
+            &[5952.0, -11648.0, 9152.0, -2574.0, 135135.0],
+            &[-64.0, -624.0, 4576.0, -6864.0, 3003.0, 135135.0],
+            &[8.0, 10725.0],
+            &[1856.0, -936.0, 225225.0],
+            &[-8448.0, 4992.0, -1144.0, 225225.0],
+            &[-1440.0, 4160.0, -4576.0, 1716.0, 225225.0],
+            &[-136.0, 63063.0],
+            &[1024.0, -208.0, 105105.0],
+            &[3584.0, -3328.0, 1144.0, 315315.0],
+            &[-128.0, 135135.0],
+            &[-2560.0, 832.0, 405405.0],
+            &[128.0, 99099.0],
+        ];
+
+        let mut a3x = [0.0; N_A3X];
+        a3x.copy_from_slice(&coeffs_in_n(&A3, n));
+        let mut c3x = [0.0; N_C3X];
+        c3x.copy_from_slice(&coeffs_in_n(&C3, n));
+        let mut c4x = [0.0; N_C4X];
+        c4x.copy_from_slice(&coeffs_in_n(&C4, n));
+
+        Ok(Geodesic {
+            ellipsoid: *ellipsoid,
+            a,
+            f,
+            f1,
+            e2: ellipsoid.eccentricity_sq(),
+            ep2,
+            n,
+            b,
+            c2: c * c,
+            etol2,
+            a3x,
+            c3x,
+            c4x,
+        })
+    }
+
+    /// The ellipsoid the geodesics are on.
+    pub fn ellipsoid(&self) -> &Ellipsoid {
+        &self.ellipsoid
+    }
+
+    // The authalic radius squared, so that the area of the ellipsoid is 4πc².
+    pub(crate) fn c2(&self) -> f64 {
+        self.c2
+    }
+
+    fn a3f(&self, eps: f64) -> f64 {
+        polyval(&self.a3x, eps)
+    }
+
+    // C₃ₗ, at c[l] for l = 1 .. 5.
+    fn c3f(&self, eps: f64) -> [f64; ORDER] {
+        let mut c = [0.0; ORDER];
+        let mut mult = 1.0;
+        let mut o = 0;
+        for (l, cl) in c.iter_mut().enumerate().skip(1) {
+            let m = ORDER - l;
+            mult *= eps;
+            *cl = mult * polyval(&self.c3x[o..o + m], eps);
+            o += m;
+        }
+        c
+    }
+
+    // C₄ₗ, at c[l] for l = 0 .. 5.
+    fn c4f(&self, eps: f64) -> [f64; ORDER] {
+        let mut c = [0.0; ORDER];
+        let mut mult = 1.0;
+        let mut o = 0;
+        for (l, cl) in c.iter_mut().enumerate() {
+            let m = ORDER - l;
+            *cl = mult * polyval(&self.c4x[o..o + m], eps);
+            o += m;
+            mult *= eps;
+        }
+        c
+    }
+
+    // The area S₁₂ between the geodesic and the equator, with the points
+    // arranged as in the inverse problem, from the reduced latitudes, the
+    // azimuths at each point and ω₁₂ when it's known and not on a meridian.
+    fn area(
+        &self,
+        (sbet1, cbet1): (f64, f64),
+        (sbet2, cbet2): (f64, f64),
+        (salp1, calp1): (f64, f64),
+        (salp2, calp2): (f64, f64),
+        omg12: Option<(f64, f64)>,
+    ) -> f64 {
+        // sin α₁ cos β₁ = sin α₀
+        let salp0 = salp1 * cbet1;
+        let calp0 = calp1.hypot(salp1 * sbet1);
+
+        // Avoid indeterminate σ₁ and σ₂ on the equator.
+        let s12 = if calp0 != 0.0 && salp0 != 0.0 {
+            // tan β = tan σ cos α
+            let (ssig1, csig1) = norm2(sbet1, calp1 * cbet1);
+            let (ssig2, csig2) = norm2(sbet2, calp2 * cbet2);
+            let k2 = sq(calp0) * self.ep2;
+            let eps = k2 / (2.0 * (1.0 + (1.0 + k2).sqrt()) + k2);
+            let a4 = sq(self.a) * calp0 * salp0 * self.e2;
+            let ca = self.c4f(eps);
+            let b41 = sin_cos_series(false, ssig1, csig1, &ca);
+            let b42 = sin_cos_series(false, ssig2, csig2, &ca);
+            a4 * (b42 - b41)
+        } else {
+            0.0
+        };
+
+        // The spherical excess, α₁₂ = α₂ - α₁.
+        let alp12 = match omg12 {
+            // With ω₁₂ < 3π/4 and the latitudes not too far apart.
+            Some((somg12, comg12)) if comg12 > -FRAC_1_SQRT_2 && sbet2 - sbet1 < 1.75 => {
+                // tan(Γ/2) = tan(ω₁₂/2) (tan(β₁/2) + tan(β₂/2)) / (1 + tan(β₁/2) tan(β₂/2))
+                let domg12 = 1.0 + comg12;
+                let dbet1 = 1.0 + cbet1;
+                let dbet2 = 1.0 + cbet2;
+                2.0 * (somg12 * (sbet1 * dbet2 + sbet2 * dbet1))
+                    .atan2(domg12 * (sbet1 * sbet2 + dbet1 * dbet2))
+            }
+            _ => {
+                let salp12 = salp2 * calp1 - calp2 * salp1;
+                let calp12 = calp2 * calp1 + salp2 * salp1;
+                // Keep the sign of zero for α₁ = ±180° and α₂ = 0.
+                if salp12 == 0.0 && calp12 < 0.0 {
+                    (TINY * calp1).atan2(-1.0)
+                } else {
+                    salp12.atan2(calp12)
+                }
+            }
+        };
+
+        s12 + self.c2 * alp12
+    }
+
+    // A starting point for Newton's method, (sin α₁, cos α₁), and the solution
+    // of a short line when Newton's method isn't needed.
+    fn inverse_start(
+        &self,
+        (sbet1, cbet1): (f64, f64),
+        (sbet2, cbet2): (f64, f64),
+        lam12: f64,
+        slam12: f64,
+        clam12: f64,
+    ) -> ((f64, f64), Option<ShortLine>) {
+        let sbet12 = sbet2 * cbet1 - cbet2 * sbet1;
+        let cbet12 = cbet2 * cbet1 + sbet2 * sbet1;
+        let sbet12a = sbet2 * cbet1 + cbet2 * sbet1;
+        let shortline = cbet12 >= 0.0 && sbet12 < 0.5 && cbet2 * lam12 < 0.5;
+
+        let mut dnm = 0.0;
+        let (mut somg12, mut comg12) = if shortline {
+            let sbetm2 = sq(sbet1 + sbet2);
+            let sbetm2 = sbetm2 / (sbetm2 + sq(cbet1 + cbet2));
+            dnm = (1.0 + self.ep2 * sbetm2).sqrt();
+            let omg12 = lam12 / (self.f1 * dnm);
+            omg12.sin_cos()
+        } else {
+            (slam12, clam12)
+        };
+
+        let mut salp1 = cbet2 * somg12;
+        let mut calp1 = if comg12 >= 0.0 {
+            sbet12 + cbet2 * sbet1 * sq(somg12) / (1.0 + comg12)
+        } else {
+            sbet12a - cbet2 * sbet1 * sq(somg12) / (1.0 - comg12)
+        };
+
+        let ssig12 = salp1.hypot(calp1);
+        let csig12 = sbet1 * sbet2 + cbet1 * cbet2 * comg12;
+
+        if shortline && ssig12 < self.etol2 {
+            // Really short lines.
+            let salp2 = cbet1 * somg12;
+            let calp2 = sbet12
+                - cbet1
+                    * sbet2
+                    * if comg12 >= 0.0 {
+                        sq(somg12) / (1.0 + comg12)
+                    } else {
+                        1.0 - comg12
+                    };
+            let (salp1, calp1) = norm2(salp1, calp1);
+            return (
+                (salp1, calp1),
+                Some(ShortLine {
+                    alp2: norm2(salp2, calp2),
+                    sig12: ssig12.atan2(csig12),
+                    dnm,
+                }),
+            );
+        } else if self.n.abs() > 0.1
+            || csig12 >= 0.0
+            || ssig12 >= 6.0 * self.n.abs() * PI * sq(cbet1)
+        {
+            // The zeroth order spherical approximation is good enough.
+        } else {
+            // Scale λ₁₂ and β₂ to an x, y coordinate system with the antipodal
+            // point at the origin and the singular point at y = 0, x = -1.
+            let lam12x = (-slam12).atan2(-clam12);
+            let k2 = sq(sbet1) * self.ep2;
+            let eps = k2 / (2.0 * (1.0 + (1.0 + k2).sqrt()) + k2);
+            let lamscale = self.f * cbet1 * self.a3f(eps) * PI;
+            let betscale = lamscale * cbet1;
+            let x = lam12x / lamscale;
+            let y = sbet12a / betscale;
+
+            if y > -TOL1 && x > -1.0 - XTHRESH {
+                salp1 = (-x).min(1.0);
+                calp1 = -(1.0 - sq(salp1)).sqrt();
+            } else {
+                let k = astroid(x, y);
+                let omg12a = lamscale * (-x * k / (1.0 + k));
+                somg12 = omg12a.sin();
+                comg12 = -omg12a.cos();
+                salp1 = cbet2 * somg12;
+                calp1 = sbet12a - cbet2 * sbet1 * sq(somg12) / (1.0 - comg12);
+            }
+        }
+
+        // A sanity check that lets NaN through.
+        let alp1 = if salp1 > 0.0 || salp1.is_nan() {
+            norm2(salp1, calp1)
+        } else {
+            (1.0, 0.0)
+        };
+        (alp1, None)
+    }
+
+    // The difference of λ₁₂ for the geodesic leaving at α₁ from the target λ₁₂
+    // and the derivative of this with respect to α₁.
+    #[allow(clippy::too_many_arguments)]
+    fn lambda12(
+        &self,
+        (sbet1, cbet1, dn1): (f64, f64, f64),
+        (sbet2, cbet2, dn2): (f64, f64, f64),
+        salp1: f64,
+        calp1: f64,
+        slam120: f64,
+        clam120: f64,
+        diffp: bool,
+    ) -> Lambda12 {
+        // Break the degeneracy of an equatorial line.
+        let calp1 = if sbet1 == 0.0 && calp1 == 0.0 {
+            -TINY
+        } else {
+            calp1
+        };
+
+        let salp0 = salp1 * cbet1;
+        let calp0 = calp1.hypot(salp1 * sbet1);
+
+        let somg1 = salp0 * sbet1;
+        let comg1 = calp1 * cbet1;
+        let (ssig1, csig1) = norm2(sbet1, comg1);
+
+        // Enforce symmetries in the case |β₂| = -β₁.
+        let salp2 = if cbet2 != cbet1 { salp0 / cbet2 } else { salp1 };
+        let calp2 = if cbet2 != cbet1 || sbet2.abs() != -sbet1 {
+            (sq(calp1 * cbet1)
+                + if cbet1 < -sbet1 {
+                    (cbet2 - cbet1) * (cbet1 + cbet2)
+                } else {
+                    (sbet1 - sbet2) * (sbet1 + sbet2)
+                })
+            .sqrt()
+                / cbet2
+        } else {
+            calp1.abs()
+        };
+
+        let somg2 = salp0 * sbet2;
+        let comg2 = calp2 * cbet2;
+        let (ssig2, csig2) = norm2(sbet2, comg2);
+
+        // σ₁₂ = σ₂ - σ₁ and ω₁₂ = ω₂ - ω₁, limited to 0 .. π.
+        let sig12 =
+            ((csig1 * ssig2 - ssig1 * csig2).max(0.0) + 0.0).atan2(csig1 * csig2 + ssig1 * ssig2);
+        let somg12 = (comg1 * somg2 - somg1 * comg2).max(0.0) + 0.0;
+        let comg12 = comg1 * comg2 + somg1 * somg2;
+
+        // η = ω₁₂ - λ₁₂
+        let eta = (somg12 * clam120 - comg12 * slam120).atan2(comg12 * clam120 + somg12 * slam120);
+        let k2 = sq(calp0) * self.ep2;
+        let eps = k2 / (2.0 * (1.0 + (1.0 + k2).sqrt()) + k2);
+        let ca = self.c3f(eps);
+        let b312 =
+            sin_cos_series(true, ssig2, csig2, &ca) - sin_cos_series(true, ssig1, csig1, &ca);
+        let domg12 = -self.f * self.a3f(eps) * salp0 * (sig12 + b312);
+        let lam12 = eta + domg12;
+
+        let dlam12 = if !diffp {
+            0.0
+        } else if calp2 == 0.0 {
+            -2.0 * self.f1 * dn1 / sbet1
+        } else {
+            let lengths = lengths(eps, sig12, (ssig1, csig1, dn1), (ssig2, csig2, dn2));
+            lengths.m12b * self.f1 / (calp2 * cbet2)
+        };
+
+        Lambda12 {
+            lam12,
+            dlam12,
+            domg12,
+            salp2,
+            calp2,
+            sig12,
+            ssig1,
+            csig1,
+            ssig2,
+            csig2,
+            eps,
+        }
+    }
+
+    /// Solves the inverse problem between points given in degrees.
+    pub(crate) fn inverse_deg(&self, lat1: f64, lng1: f64, lat2: f64, lng2: f64) -> Inverse {
+        self.gen_inverse(lat1, lng1, lat2, lng2, false)
+    }
+
+    // Solves the inverse problem between points given in degrees and, with
+    // with_area, finds the area S₁₂ too.
+    pub(crate) fn gen_inverse(
+        &self,
+        lat1: f64,
+        lng1: f64,
+        lat2: f64,
+        lng2: f64,
+        with_area: bool,
+    ) -> Inverse {
+        // The longitude difference, in -180° .. 180° with -180° only for west
+        // going geodesics, made positive.
+        let (lon12, lon12s) = ang_diff(lng1, lng2);
+        let mut lonsign: f64 = if lon12 >= 0.0 { 1.0 } else { -1.0 };
+        let lon12 = lonsign * ang_round(lon12);
+        let lon12s = ang_round((180.0 - lon12) - lonsign * lon12s);
+        let lam12 = lon12.to_radians();
+        let (slam12, clam12) = if lon12 > 90.0 {
+            let (s, c) = sincosd(lon12s);
+            (s, -c)
+        } else {
+            sincosd(lon12)
+        };
+
+        // Really close to the equator is treated as on the equator.
+        let mut lat1 = ang_round(lat1);
+        let mut lat2 = ang_round(lat2);
+
+        // Swap the points so that the first has the larger absolute latitude
+        // and then make that latitude negative, so that we have
+        //   0 <= λ₁₂ <= 180°, -90° <= φ₁ <= -0° and φ₁ <= φ₂ <= -φ₁
+        let swapp: f64 = if lat1.abs() < lat2.abs() { -1.0 } else { 1.0 };
+        if swapp < 0.0 {
+            lonsign *= -1.0;
+            std::mem::swap(&mut lat1, &mut lat2);
+        }
+        let latsign: f64 = if lat1.is_sign_negative() { 1.0 } else { -1.0 };
+        lat1 *= latsign;
+        lat2 *= latsign;
+
+        // Keep cos β = +ε at the poles.
+        let (sbet1, cbet1) = sincosd(lat1);
+        let (sbet1, cbet1) = norm2(sbet1 * self.f1, cbet1);
+        let cbet1 = cbet1.max(TINY);
+
+        let (sbet2, cbet2) = sincosd(lat2);
+        let (mut sbet2, cbet2) = norm2(sbet2 * self.f1, cbet2);
+        let mut cbet2 = cbet2.max(TINY);
+
+        // Force β₂ = ±β₁ exactly when the measure of |β₁| - |β₂| vanishes.
+        if cbet1 < -sbet1 {
+            if cbet2 == cbet1 {
+                sbet2 = sbet1.copysign(sbet2);
+            }
+        } else if sbet2.abs() == -sbet1 {
+            cbet2 = cbet1;
+        }
+
+        let dn1 = (1.0 + self.ep2 * sq(sbet1)).sqrt();
+        let dn2 = (1.0 + self.ep2 * sq(sbet2)).sqrt();
+
+        let mut salp1 = 0.0;
+        let mut calp1 = 0.0;
+        let mut salp2 = 0.0;
+        let mut calp2 = 0.0;
+        let mut s12x = 0.0;
+        let mut sig12 = 0.0;
+        let mut omg12 = None;
+        let mut iterations = 0;
+
+        let mut meridian = lat1 == -90.0 || slam12 == 0.0;
+        if meridian {
+            // The endpoints are on a single full meridian, heading to the
+            // target longitude and arriving heading north.
+            calp1 = clam12;
+            salp1 = slam12;
+            calp2 = 1.0;
+            salp2 = 0.0;
+
+            let ssig1 = sbet1;
+            let csig1 = calp1 * cbet1;
+            let ssig2 = sbet2;
+            let csig2 = calp2 * cbet2;
+
+            sig12 = ((csig1 * ssig2 - ssig1 * csig2).max(0.0) + 0.0)
+                .atan2(csig1 * csig2 + ssig1 * ssig2);
+            let lengths = lengths(self.n, sig12, (ssig1, csig1, dn1), (ssig2, csig2, dn2));
+
+            // With σ₁₂ > π/2 a meridional geodesic is not the shortest path.
+            if sig12 < 1.0 || lengths.m12b >= 0.0 {
+                s12x = if sig12 < 3.0 * TINY
+                    || (sig12 < TOL0 && (lengths.s12b < 0.0 || lengths.m12b < 0.0))
+                {
+                    0.0
+                } else {
+                    lengths.s12b * self.b
+                };
+            } else {
+                meridian = false;
+            }
+        }
+
+        if !meridian && sbet1 == 0.0 && (self.f <= 0.0 || lon12s >= self.f * 180.0) {
+            // The geodesic runs along the equator.
+            calp1 = 0.0;
+            calp2 = 0.0;
+            salp1 = 1.0;
+            salp2 = 1.0;
+            s12x = self.a * lam12;
+            sig12 = lam12 / self.f1;
+            omg12 = Some(sig12.sin_cos());
+        } else if !meridian {
+            let ((s1, c1), short) =
+                self.inverse_start((sbet1, cbet1), (sbet2, cbet2), lam12, slam12, clam12);
+            salp1 = s1;
+            calp1 = c1;
+
+            if let Some(ShortLine {
+                alp2,
+                sig12: s,
+                dnm,
+            }) = short
+            {
+                (salp2, calp2) = alp2;
+                sig12 = s;
+                s12x = sig12 * self.b * dnm;
+                omg12 = Some((lam12 / (self.f1 * dnm)).sin_cos());
+            } else {
+                // Newton's method on λ₁₂(α₁) - λ₁₂ = 0 with a single root in
+                // 0 .. π, maintaining a bracket of the root and bisecting it
+                // whenever Newton's method goes astray.
+                let mut salp1a = TINY;
+                let mut calp1a = 1.0;
+                let mut salp1b = TINY;
+                let mut calp1b = -1.0;
+                let mut tripn = false;
+                let mut tripb = false;
+                let mut numit = 0;
+                let mut v;
+
+                loop {
+                    v = self.lambda12(
+                        (sbet1, cbet1, dn1),
+                        (sbet2, cbet2, dn2),
+                        salp1,
+                        calp1,
+                        slam12,
+                        clam12,
+                        numit < MAXIT1,
+                    );
+                    iterations = numit + 1;
+
+                    // Escaping with NaN too.
+                    let tol = if tripn { 8.0 } else { 1.0 } * TOL0;
+                    if tripb || v.lam12.abs() < tol || v.lam12.is_nan() || numit == MAXIT2 {
+                        break;
+                    }
+
+                    if v.lam12 > 0.0 && (numit > MAXIT1 || calp1 / salp1 > calp1b / salp1b) {
+                        salp1b = salp1;
+                        calp1b = calp1;
+                    } else if v.lam12 < 0.0 && (numit > MAXIT1 || calp1 / salp1 < calp1a / salp1a) {
+                        salp1a = salp1;
+                        calp1a = calp1;
+                    }
+
+                    numit += 1;
+                    if numit <= MAXIT1 && v.dlam12 > 0.0 {
+                        let dalp1 = -v.lam12 / v.dlam12;
+                        if dalp1.abs() < PI {
+                            let (sdalp1, cdalp1) = dalp1.sin_cos();
+                            let nsalp1 = salp1 * cdalp1 + calp1 * sdalp1;
+                            if nsalp1 > 0.0 {
+                                (salp1, calp1) = norm2(nsalp1, calp1 * cdalp1 - salp1 * sdalp1);
+                                tripn = v.lam12.abs() <= 16.0 * TOL0;
+                                continue;
+                            }
+                        }
+                    }
+
+                    (salp1, calp1) = norm2((salp1a + salp1b) / 2.0, (calp1a + calp1b) / 2.0);
+                    tripn = false;
+                    tripb = (salp1a - salp1).abs() + (calp1a - calp1) < TOLB
+                        || (salp1 - salp1b).abs() + (calp1 - calp1b) < TOLB;
+                }
+
+                salp2 = v.salp2;
+                calp2 = v.calp2;
+                let lengths = lengths(
+                    v.eps,
+                    v.sig12,
+                    (v.ssig1, v.csig1, dn1),
+                    (v.ssig2, v.csig2, dn2),
+                );
+                s12x = lengths.s12b * self.b;
+                sig12 = v.sig12;
+
+                // ω₁₂ = λ₁₂ - Δω₁₂
+                let (sdomg12, cdomg12) = v.domg12.sin_cos();
+                omg12 = Some((
+                    slam12 * cdomg12 - clam12 * sdomg12,
+                    clam12 * cdomg12 + slam12 * sdomg12,
+                ));
+            }
+        }
+
+        let area = if with_area {
+            let s12 = self.area(
+                (sbet1, cbet1),
+                (sbet2, cbet2),
+                (salp1, calp1),
+                (salp2, calp2),
+                if meridian { None } else { omg12 },
+            );
+            Some(s12 * swapp * lonsign * latsign + 0.0)
+        } else {
+            None
+        };
+
+        // Restore the arrangement of the points.
+        if swapp < 0.0 {
+            std::mem::swap(&mut salp1, &mut salp2);
+            std::mem::swap(&mut calp1, &mut calp2);
+        }
+
+        salp1 *= swapp * lonsign;
+        calp1 *= swapp * latsign;
+        salp2 *= swapp * lonsign;
+        calp2 *= swapp * latsign;
+
+        Inverse {
+            s12: s12x + 0.0,
+            azi1: atan2d(salp1, calp1),
+            azi2: atan2d(salp2, calp2),
+            area,
+            iterations,
+            sig12,
+        }
+    }
+
+    /// The geodesic leaving a point at an azimuth, all in degrees.
+    pub(crate) fn line(&self, lat1: f64, lng1: f64, azi1: f64) -> Line {
+        let (salp1, calp1) = sincosd(ang_round(azi1));
+
+        // Keep cos β₁ = +ε at the poles.
+        let (sbet1, cbet1) = sincosd(ang_round(lat1));
+        let (sbet1, cbet1) = norm2(sbet1 * self.f1, cbet1);
+        let cbet1 = cbet1.max(TINY);
+
+        // sin α₁ cos β₁ = sin α₀
+        let salp0 = salp1 * cbet1;
+        let calp0 = calp1.hypot(salp1 * sbet1);
+
+        // From tan β₁ = tan σ₁ cos α₁ and tan ω₁ = sin α₀ tan σ₁, with σ = 0
+        // at the northward crossing of the equator.
+        let somg1 = salp0 * sbet1;
+        let comg1 = if sbet1 != 0.0 || calp1 != 0.0 {
+            cbet1 * calp1
+        } else {
+            1.0
+        };
+        let (ssig1, csig1) = norm2(sbet1, comg1);
+
+        let k2 = sq(calp0) * self.ep2;
+        let eps = k2 / (2.0 * (1.0 + (1.0 + k2).sqrt()) + k2);
+
+        let a1m1 = a1m1f(eps);
+        let c1a = c1f(eps);
+        let b11 = sin_cos_series(true, ssig1, csig1, &c1a);
+        let (s, c) = b11.sin_cos();
+        let stau1 = ssig1 * c + csig1 * s;
+        let ctau1 = csig1 * c - ssig1 * s;
+
+        let c1pa = c1pf(eps);
+
+        let c3a = self.c3f(eps);
+        let a3c = -self.f * salp0 * self.a3f(eps);
+        let b31 = sin_cos_series(true, ssig1, csig1, &c3a);
+
+        Line {
+            lng1,
+            b: self.b,
+            f1: self.f1,
+            salp0,
+            calp0,
+            k2,
+            ssig1,
+            csig1,
+            stau1,
+            ctau1,
+            somg1,
+            comg1,
+            a1m1,
+            a3c,
+            b11,
+            b31,
+            c1a,
+            c1pa,
+            c3a,
+        }
+    }
+
+    // The quantities of the geodesic leaving latitude φ₁ at azimuth α₁, in
+    // degrees, for an arc σ₁₂ on the auxiliary sphere.
+    fn aux(&self, lat1: f64, azi1: f64, sig12: f64) -> GeodesicAux {
+        let (sbet1, cbet1) = sincosd(ang_round(lat1));
+        let (sbet1, cbet1) = norm2(sbet1 * self.f1, cbet1);
+        geodesic_aux(
+            self.ep2,
+            self.b,
+            (sbet1, cbet1.max(TINY)),
+            sincosd(ang_round(azi1)),
+            sig12,
+        )
+    }
+
+    /// Solves the direct problem, to the nanometre, for any distance.
+    pub fn direct(&self, p: &DirectProblem) -> Result<DirectSolution, GeodesyError> {
+        let Rad(lat1) = check_problem(&self.ellipsoid, p.x.lat)?;
+        let line = self.line(
+            rad_to_deg(Rad(lat1)).0,
+            rad_to_deg(p.x.lng).0,
+            rad_to_deg(Rad(p.az1.az)).0,
+        );
+        let pos = line.position(p.s.dist);
+
+        Ok(DirectSolution {
+            y: LatLng::new(pos.lat2, pos.lng2),
+            az2: Some(azimuth(pos.azi2)),
+            iterations: 0,
+            aux: Some(self.aux(
+                rad_to_deg(Rad(lat1)).0,
+                rad_to_deg(Rad(p.az1.az)).0,
+                pos.sig12,
+            )),
+        })
+    }
+
+    /// Solves the inverse problem, to the nanometre, for any pair of points.
+    pub fn inverse(&self, p: &InverseProblem) -> Result<InverseSolution, GeodesyError> {
+        let lat1 = check_problem(&self.ellipsoid, p.x.lat)?;
+        let lat2 = check_problem(&self.ellipsoid, p.y.lat)?;
+        let lat1 = rad_to_deg(lat1).0;
+        let inv = self.inverse_deg(
+            lat1,
+            rad_to_deg(p.x.lng).0,
+            rad_to_deg(lat2).0,
+            rad_to_deg(p.y.lng).0,
+        );
+
+        Ok(InverseSolution {
+            s: Dist { dist: inv.s12 },
+            az1: azimuth(inv.azi1),
+            az2: Some(azimuth(inv.azi2)),
+            iterations: inv.iterations,
+            aux: Some(self.aux(lat1, inv.azi1, inv.sig12)),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Lambda12 {
+    lam12: f64,
+    dlam12: f64,
+    domg12: f64,
+    salp2: f64,
+    calp2: f64,
+    sig12: f64,
+    ssig1: f64,
+    csig1: f64,
+    ssig2: f64,
+    csig2: f64,
+    eps: f64,
+}
+
+impl Line {
+    /// The position at a distance along the geodesic.
+    pub(crate) fn position(&self, s12: f64) -> Position {
+        let tau12 = s12 / (self.b * (1.0 + self.a1m1));
+        let (s, c) = tau12.sin_cos();
+        let b12 = -sin_cos_series(
+            true,
+            self.stau1 * c + self.ctau1 * s,
+            self.ctau1 * c - self.stau1 * s,
+            &self.c1pa,
+        );
+        let mut sig12 = tau12 - (b12 - self.b11);
+        let (mut ssig12, mut csig12) = sig12.sin_cos();
+
+        // The reverted distance series is inaccurate for |f| > 1/100 so correct
+        // σ₁₂ with a Newton iteration.
+        if (1.0 - self.f1).abs() > 0.01 {
+            let ssig2 = self.ssig1 * csig12 + self.csig1 * ssig12;
+            let csig2 = self.csig1 * csig12 - self.ssig1 * ssig12;
+            let b12 = sin_cos_series(true, ssig2, csig2, &self.c1a);
+            let serr = (1.0 + self.a1m1) * (sig12 + (b12 - self.b11)) - s12 / self.b;
+            sig12 -= serr / (1.0 + self.k2 * sq(ssig2)).sqrt();
+            (ssig12, csig12) = sig12.sin_cos();
+        }
+
+        // σ₂ = σ₁ + σ₁₂
+        let ssig2 = self.ssig1 * csig12 + self.csig1 * ssig12;
+        let mut csig2 = self.csig1 * csig12 - self.ssig1 * ssig12;
+
+        // sin β₂ = cos α₀ sin σ₂
+        let sbet2 = self.calp0 * ssig2;
+        let mut cbet2 = self.salp0.hypot(self.calp0 * csig2);
+        if cbet2 == 0.0 {
+            // Break the degeneracy of sin α₀ = 0 and cos σ₂ = 0.
+            cbet2 = TINY;
+            csig2 = TINY;
+        }
+
+        // tan α₀ = cos σ₂ tan α₂
+        let salp2 = self.salp0;
+        let calp2 = self.calp0 * csig2;
+
+        // tan ω₂ = sin α₀ tan σ₂
+        let somg2 = self.salp0 * ssig2;
+        let comg2 = csig2;
+        let omg12 = (somg2 * self.comg1 - comg2 * self.somg1)
+            .atan2(comg2 * self.comg1 + somg2 * self.somg1);
+        let lam12 =
+            omg12 + self.a3c * (sig12 + (sin_cos_series(true, ssig2, csig2, &self.c3a) - self.b31));
+
+        Position {
+            lat2: atan2d(sbet2, self.f1 * cbet2),
+            lng2: ang_normalize(ang_normalize(self.lng1) + ang_normalize(lam12.to_degrees())),
+            azi2: atan2d(salp2, calp2),
+            sig12,
+        }
+    }
+}
+
+fn lengths(
+    eps: f64,
+    sig12: f64,
+    (ssig1, csig1, dn1): (f64, f64, f64),
+    (ssig2, csig2, dn2): (f64, f64, f64),
+) -> Lengths {
+    let a1 = a1m1f(eps);
+    let ca = c1f(eps);
+    let a2 = a2m1f(eps);
+    let cb = c2f(eps);
+    let m0 = a1 - a2;
+    let a1 = 1.0 + a1;
+    let a2 = 1.0 + a2;
+
+    let b1 = sin_cos_series(true, ssig2, csig2, &ca) - sin_cos_series(true, ssig1, csig1, &ca);
+    let b2 = sin_cos_series(true, ssig2, csig2, &cb) - sin_cos_series(true, ssig1, csig1, &cb);
+    let s12b = a1 * (sig12 + b1);
+    let j12 = m0 * sig12 + (a1 * b1 - a2 * b2);
+
+    // Parentheses around (csig1 * ssig2) and (ssig1 * csig2) ensure
+    // accurate cancellation for coincident points.
+    let m12b = dn2 * (csig1 * ssig2) - dn1 * (ssig1 * csig2) - csig1 * csig2 * j12;
+
+    Lengths { s12b, m12b, j12 }
+}
+
+// The arc length σ₁₂, equatorial azimuth, reduced length and geodesic scales of
+// the geodesic leaving reduced latitude β₁ at azimuth α₁, each as (sin, cos),
+// on an ellipsoid with second eccentricity squared e'² and polar radius b.
+pub(crate) fn geodesic_aux(
+    ep2: f64,
+    b: f64,
+    (sbet1, cbet1): (f64, f64),
+    (salp1, calp1): (f64, f64),
+    sig12: f64,
+) -> GeodesicAux {
+    let salp0 = salp1 * cbet1;
+    let calp0 = calp1.hypot(salp1 * sbet1);
+    let comg1 = if sbet1 != 0.0 || calp1 != 0.0 {
+        cbet1 * calp1
+    } else {
+        1.0
+    };
+    let (ssig1, csig1) = norm2(sbet1, comg1);
+    let (ssig12, csig12) = sig12.sin_cos();
+    let ssig2 = ssig1 * csig12 + csig1 * ssig12;
+    let csig2 = csig1 * csig12 - ssig1 * ssig12;
+
+    // sin β₂ = cos α₀ sin σ₂
+    let cbet2 = salp0.hypot(calp0 * csig2);
+    let k2 = sq(calp0) * ep2;
+    let eps = k2 / (2.0 * (1.0 + (1.0 + k2).sqrt()) + k2);
+    let dn1 = (1.0 + k2 * sq(ssig1)).sqrt();
+    let dn2 = (1.0 + k2 * sq(ssig2)).sqrt();
+    let lengths = lengths(eps, sig12, (ssig1, csig1, dn1), (ssig2, csig2, dn2));
+
+    let t = ep2 * (cbet1 - cbet2) * (cbet1 + cbet2) / (dn1 + dn2);
+    let scale12 = csig12 + (t * ssig2 - csig2 * lengths.j12) * ssig1 / dn1;
+    let scale21 = csig12 - (t * ssig1 - csig1 * lengths.j12) * ssig2 / dn2;
+
+    GeodesicAux {
+        sigma12: Rad(sig12),
+        alpha0: Az {
+            az: Rad(salp0.atan2(calp0)).normalize().0,
+        },
+        m12: Dist {
+            dist: lengths.m12b * b,
+        },
+        scale12,
+        scale21,
+    }
+}
+
+// An azimuth in degrees normalized to 0° .. 360°.
+fn azimuth(deg: f64) -> Az {
+    Az {
+        az: deg_to_rad(Deg(deg)).normalize().0,
+    }
+}
+
+/// The solution to the direct geodesy problem by Karney's method.
+pub fn direct(ellipsoid: &Ellipsoid, p: &DirectProblem) -> Result<DirectSolution, GeodesyError> {
+    Geodesic::new(ellipsoid)?.direct(p)
+}
+
+/// The solution to the inverse geodesy problem by Karney's method. Unlike
+/// Vincenty's method this converges for nearly antipodal points.
+pub fn inverse(ellipsoid: &Ellipsoid, p: &InverseProblem) -> Result<InverseSolution, GeodesyError> {
+    Geodesic::new(ellipsoid)?.inverse(p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::earth::ellipsoid::WGS84;
+
+    #[test]
+    fn direct_example() {
+        // The example of Karney's Algorithms for geodesics, 2013, table 2.
+        let prob = DirectProblem {
+            x: LatLng::new(40.0, 0.0),
+            az1: Az {
+                az: deg_to_rad(Deg(30.0)).0,
+            },
+            s: Dist { dist: 10000000.0 },
+        };
+        let DirectSolution { y, az2, aux, .. } = direct(&WGS84, &prob).unwrap();
+        let aux = aux.unwrap();
+
+        assert!((rad_to_deg(y.lat).0 - 41.79331020506).abs() < 1e-11);
+        assert!((rad_to_deg(y.lng).0 - 137.84490004377).abs() < 1e-11);
+        assert!((rad_to_deg(Rad(az2.unwrap().az)).0 - 149.09016931807).abs() < 1e-11);
+        assert!((rad_to_deg(Rad(aux.alpha0.az)).0 - 22.55394020262).abs() < 1e-11);
+        assert!((rad_to_deg(aux.sigma12).0 - (133.92164083038 - 43.99915364500)).abs() < 1e-10);
+    }
+
+    #[test]
+    fn nearly_antipodal() {
+        // The example of Karney's Algorithms for geodesics, 2013, table 3.
+        let prob = InverseProblem {
+            x: LatLng::new(-30.0, 0.0),
+            y: LatLng::new(29.9, 179.8),
+        };
+        let InverseSolution {
+            s, az1, az2, aux, ..
+        } = inverse(&WGS84, &prob).unwrap();
+        let aux = aux.unwrap();
+
+        assert!((s.dist - 19989832.827610).abs() < 1e-6);
+        assert!((rad_to_deg(Rad(az1.az)).0 - 161.890524736).abs() < 1e-9);
+        assert!((rad_to_deg(Rad(az2.unwrap().az)).0 - 18.090737246).abs() < 1e-9);
+        assert!((rad_to_deg(Rad(aux.alpha0.az)).0 - 15.629479665).abs() < 1e-9);
+        assert!((rad_to_deg(aux.sigma12).0 - 179.894971388).abs() < 1e-9);
+    }
+
+    #[test]
+    fn reduced_length_rate() {
+        // Along the geodesic, dm₁₂/ds₁₂ = M₂₁.
+        let geodesic = Geodesic::new(&WGS84).unwrap();
+        let at = |dist| {
+            geodesic
+                .direct(&DirectProblem {
+                    x: LatLng::new(-20.0, 30.0),
+                    az1: Az {
+                        az: deg_to_rad(Deg(70.0)).0,
+                    },
+                    s: Dist { dist },
+                })
+                .unwrap()
+                .aux
+                .unwrap()
+        };
+
+        for dist in [1e5, 5e6, 1.5e7, 3e7] {
+            let rate = (at(dist + 1.0).m12.dist - at(dist - 1.0).m12.dist) / 2.0;
+            assert!((rate - at(dist).scale21).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let geodesic = Geodesic::new(&WGS84).unwrap();
+        for (x, y) in [
+            (LatLng::new(-30.0, 0.0), LatLng::new(29.9, 179.8)),
+            (LatLng::new(0.0, 0.0), LatLng::new(0.5, 179.5)),
+            (LatLng::new(89.0, 10.0), LatLng::new(-89.0, -170.0)),
+            (LatLng::new(51.5, -0.1), LatLng::new(40.7, -74.0)),
+        ] {
+            let inv = geodesic.inverse(&InverseProblem { x, y }).unwrap();
+            let DirectSolution { y: z, .. } = geodesic
+                .direct(&DirectProblem {
+                    x,
+                    az1: inv.az1,
+                    s: inv.s,
+                })
+                .unwrap();
+            let miss = geodesic.inverse(&InverseProblem { x: y, y: z }).unwrap();
+
+            assert!(miss.s.dist < 1e-8);
+        }
+    }
+}
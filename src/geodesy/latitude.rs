@@ -0,0 +1,386 @@
+// The auxiliary latitudes of an ellipsoid, each mapping it onto a sphere so
+// that some property is kept, such as the length of the meridian for the
+// rectifying latitude, angles for the conformal latitude and areas for the
+// authalic latitude.
+//
+// The reduced and geocentric latitudes are found from the geodetic latitude in
+// closed form. The rectifying latitude is found from the reduced latitude with
+// Karney's series in the third flattening, n, to n⁶, the series for the
+// distance along a meridian. The conformal and authalic latitudes are found in
+// closed form and reverted with Newton's method.
+//
+// The isometric latitude, ψ, is found from the conformal latitude but isn't one
+// of these. It isn't an angle, ranging over all the reals and infinite at the
+// poles, so it has its own conversions rather than being a kind of latitude.
+//
+// SEE: <https://arxiv.org/abs/2212.05818> and <https://arxiv.org/abs/1002.1417>
+use crate::{
+    earth::ellipsoid::{flattening, is_valid, Ellipsoid},
+    units::Rad,
+};
+use std::f64::consts::FRAC_PI_2;
+
+use super::{
+    error::GeodesyError,
+    karney::{c1f, c1pf, sin_cos_series, ORDER},
+    vincenty::check_lat,
+};
+
+// The most steps of Newton's method when reverting the conformal and authalic
+// latitudes, each converging quadratically from a close start.
+const MAXIT: usize = 8;
+
+/// The latitudes of a point on an ellipsoid, each an angle from the equatorial
+/// plane in -90° .. 90° that is 0° on the equator and ±90° at the poles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Latitude {
+    /// The geodetic latitude, φ, of the normal to the ellipsoid.
+    Geodetic,
+    /// The reduced, or parametric, latitude, β, on the sphere circumscribing
+    /// the ellipsoid, with tan β = (1 - ƒ) tan φ.
+    Reduced,
+    /// The geocentric latitude, θ, of the radius from the centre, with
+    /// tan θ = (1 - ƒ)² tan φ.
+    Geocentric,
+    /// The rectifying latitude, μ, in proportion to the distance along the
+    /// meridian from the equator.
+    Rectifying,
+    /// The conformal latitude, χ, of a conformal map onto a sphere.
+    Conformal,
+    /// The authalic latitude, ξ, of an equal area map onto a sphere.
+    Authalic,
+}
+
+/// The constants of an ellipsoid, derived once, for converting any number of
+/// latitudes.
+///
+/// ```
+/// # use auxillary_sphere::{
+/// #     earth::ellipsoid::WGS84,
+/// #     geodesy::latitude::{AuxiliaryLatitudes, Latitude},
+/// #     units::{convert::*, Deg},
+/// # };
+/// let lats = AuxiliaryLatitudes::new(&WGS84).unwrap();
+/// let phi = deg_to_rad(Deg(45.0));
+///
+/// let theta = lats.convert(phi, Latitude::Geodetic, Latitude::Geocentric).unwrap();
+/// assert!((rad_to_deg(theta).0 - 44.80757678).abs() < 1e-8);
+///
+/// let mu = lats.convert(phi, Latitude::Geodetic, Latitude::Rectifying).unwrap();
+/// let back = lats.convert(mu, Latitude::Rectifying, Latitude::Geodetic).unwrap();
+/// assert!((back.0 - phi.0).abs() < 1e-15);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct AuxiliaryLatitudes {
+    // 1 - ƒ
+    f1: f64,
+    e: f64,
+    e2: f64,
+    // The authalic q at the pole, q(90°), twice the ratio of the area of the
+    // ellipsoid to that of the sphere of radius a.
+    qp: f64,
+    // The coefficients of sin 2lβ in the series for the rectifying latitude
+    // from the reduced latitude, at c[l] for l = 1 .. 6, and of sin 2lμ in its
+    // reversion.
+    c1a: [f64; ORDER + 1],
+    c1pa: [f64; ORDER + 1],
+}
+
+impl AuxiliaryLatitudes {
+    pub fn new(ellipsoid: &Ellipsoid) -> Result<Self, GeodesyError> {
+        if !is_valid(ellipsoid) {
+            return Err(GeodesyError::InvalidEllipsoid);
+        }
+
+        let f = flattening(ellipsoid);
+        let n = ellipsoid.third_flattening();
+        let e2 = ellipsoid.eccentricity_sq();
+
+        let mut lats = AuxiliaryLatitudes {
+            f1: 1.0 - f,
+            e: e2.sqrt(),
+            e2,
+            qp: 0.0,
+            c1a: c1f(n),
+            c1pa: c1pf(n),
+        };
+        lats.qp = lats.q(1.0);
+        Ok(lats)
+    }
+
+    /// Converts a latitude of one kind to another, with the latitude rejected
+    /// outside the range -90° .. 90°.
+    pub fn convert(&self, lat: Rad, from: Latitude, to: Latitude) -> Result<Rad, GeodesyError> {
+        let Rad(lat) = check_lat(lat)?;
+        if from == to {
+            return Ok(Rad(lat));
+        }
+
+        let phi = self.geodetic_from(lat, from);
+        Ok(Rad(self.geodetic_to(phi, to)))
+    }
+
+    /// The isometric latitude, ψ = sinh⁻¹(tan χ), the northing of the Mercator
+    /// projection of the ellipsoid onto a cylinder of unit radius. This is
+    /// infinite at the poles, ±∞ at ±90°.
+    ///
+    /// ```
+    /// # use auxillary_sphere::{
+    /// #     earth::ellipsoid::WGS84,
+    /// #     geodesy::latitude::AuxiliaryLatitudes,
+    /// #     units::{convert::*, Deg},
+    /// # };
+    /// let lats = AuxiliaryLatitudes::new(&WGS84).unwrap();
+    /// let phi = deg_to_rad(Deg(60.0));
+    /// let psi = lats.isometric(phi).unwrap();
+    /// assert!((lats.from_isometric(psi).0 - phi.0).abs() < 1e-15);
+    ///
+    /// let pole = deg_to_rad(Deg(-90.0));
+    /// assert_eq!(lats.isometric(pole).unwrap(), f64::NEG_INFINITY);
+    /// assert_eq!(lats.from_isometric(f64::NEG_INFINITY), pole);
+    /// ```
+    pub fn isometric(&self, lat: Rad) -> Result<f64, GeodesyError> {
+        let Rad(phi) = check_lat(lat)?;
+        // The tangent of ±90° is finite in floating point, so the poles are
+        // taken first.
+        if phi.abs() == FRAC_PI_2 {
+            return Ok(f64::INFINITY.copysign(phi));
+        }
+        Ok(self.taupf(phi.tan()).asinh())
+    }
+
+    /// The geodetic latitude from the isometric latitude, ±90° for ±∞.
+    pub fn from_isometric(&self, psi: f64) -> Rad {
+        Rad(self.tauf(psi.sinh()).atan())
+    }
+
+    // The sine and cosine of the reduced latitude from the geodetic latitude,
+    // without taking the angle.
+    pub(crate) fn reduced(&self, phi: f64) -> (f64, f64) {
+        let (sphi, cphi) = phi.sin_cos();
+        let sbeta = self.f1 * sphi;
+        let r = sbeta.hypot(cphi);
+        (sbeta / r, cphi / r)
+    }
+
+    pub(crate) fn geodetic_to(&self, phi: f64, to: Latitude) -> f64 {
+        let (sphi, cphi) = phi.sin_cos();
+        match to {
+            Latitude::Geodetic => phi,
+            Latitude::Reduced => {
+                let (sbeta, cbeta) = self.reduced(phi);
+                sbeta.atan2(cbeta)
+            }
+            Latitude::Geocentric => (self.f1 * self.f1 * sphi).atan2(cphi),
+            Latitude::Rectifying => {
+                let (sbeta, cbeta) = self.reduced(phi);
+                sbeta.atan2(cbeta) + sin_cos_series(true, sbeta, cbeta, &self.c1a)
+            }
+            Latitude::Conformal => self.taupf(phi.tan()).atan(),
+            Latitude::Authalic => self.authalic(phi),
+        }
+    }
+
+    pub(crate) fn geodetic_from(&self, lat: f64, from: Latitude) -> f64 {
+        let (slat, clat) = lat.sin_cos();
+        match from {
+            Latitude::Geodetic => lat,
+            Latitude::Reduced => slat.atan2(self.f1 * clat),
+            Latitude::Geocentric => slat.atan2(self.f1 * self.f1 * clat),
+            Latitude::Rectifying => {
+                let beta = lat + sin_cos_series(true, slat, clat, &self.c1pa);
+                beta.sin().atan2(self.f1 * beta.cos())
+            }
+            Latitude::Conformal => self.tauf(lat.tan()).atan(),
+            Latitude::Authalic => self.authalic_to_geodetic(lat),
+        }
+    }
+
+    // e tanh⁻¹(e x), or e² x for a sphere.
+    pub(crate) fn eatanhe(&self, x: f64) -> f64 {
+        if self.e == 0.0 {
+            self.e2 * x
+        } else {
+            self.e * (self.e * x).atanh()
+        }
+    }
+
+    // The tangent of the conformal latitude from that of the geodetic latitude,
+    // tan χ = tan φ √(1 + σ²) - σ √(1 + tan² φ) with σ = sinh(e tanh⁻¹(e sin φ)).
+    pub(crate) fn taupf(&self, tau: f64) -> f64 {
+        if !tau.is_finite() {
+            return tau;
+        }
+
+        let tau1 = 1.0_f64.hypot(tau);
+        let sig = self.eatanhe(tau / tau1).sinh();
+        1.0_f64.hypot(sig) * tau - sig * tau1
+    }
+
+    // The tangent of the geodetic latitude from that of the conformal latitude
+    // with Newton's method on taupf.
+    pub(crate) fn tauf(&self, taup: f64) -> f64 {
+        if !taup.is_finite() {
+            return taup;
+        }
+
+        let e2m = 1.0 - self.e2;
+        let tol = f64::EPSILON.sqrt() / 10.0 * taup.abs().max(1.0);
+        let mut tau = if taup.abs() > 70.0 {
+            taup * self.eatanhe(1.0).exp()
+        } else {
+            taup / e2m
+        };
+
+        for _ in 0..MAXIT {
+            let taupa = self.taupf(tau);
+            let dtau = (taup - taupa) * (1.0 + e2m * tau * tau)
+                / (e2m * 1.0_f64.hypot(tau) * 1.0_f64.hypot(taupa));
+            tau += dtau;
+            if dtau.abs() < tol {
+                break;
+            }
+        }
+
+        tau
+    }
+
+    // The authalic q(φ) = (1 - e²) (sin φ / (1 - e² sin² φ) + tanh⁻¹(e sin φ) / e)
+    // from sin φ, 2 sin φ for a sphere.
+    fn q(&self, sphi: f64) -> f64 {
+        let w2 = 1.0 - self.e2 * sphi * sphi;
+        let atanh_e = if self.e == 0.0 {
+            sphi
+        } else {
+            self.eatanhe(sphi) / self.e2
+        };
+        (1.0 - self.e2) * (sphi / w2 + atanh_e)
+    }
+
+    // The difference q(90°) - q(φ) for φ >= 0, from sin φ and cos φ, without
+    // the cancellation near the pole of taking the difference directly. With
+    // x = sin φ this is
+    //   (1 - x)(1 + e² x) / (1 - e² x²) + (1 - e²) tanh⁻¹(e (1 - x) / (1 - e² x)) / e
+    fn q_to_pole(&self, sphi: f64, cphi: f64) -> f64 {
+        let e2 = self.e2;
+        let x1 = cphi * cphi / (1.0 + sphi);
+        let y = x1 / (1.0 - e2 * sphi);
+        let atanh_e = if self.e == 0.0 {
+            y
+        } else {
+            self.eatanhe(y) / e2
+        };
+        x1 * (1.0 + e2 * sphi) / (1.0 - e2 * sphi * sphi) + (1.0 - e2) * atanh_e
+    }
+
+    // The authalic latitude, with sin ξ = q(φ) / q(90°) and cos ξ found from
+    // q(90°) - q(φ) to keep the accuracy near the poles.
+    fn authalic(&self, phi: f64) -> f64 {
+        let (sphi, cphi) = phi.abs().sin_cos();
+        let q = self.q(sphi);
+        let dq = self.q_to_pole(sphi, cphi);
+        q.atan2((dq * (self.qp + q)).sqrt()).copysign(phi)
+    }
+
+    // The geodetic latitude from the authalic latitude, starting from the
+    // series in e² to e⁶ and refining with Newton's method on q(90°) - q(φ).
+    fn authalic_to_geodetic(&self, xi: f64) -> f64 {
+        let e2 = self.e2;
+        let e4 = e2 * e2;
+        let e6 = e4 * e2;
+        let xi_abs = xi.abs();
+        if xi_abs >= FRAC_PI_2 {
+            return xi;
+        }
+
+        let mut phi = xi_abs
+            + (e2 / 3.0 + 31.0 * e4 / 180.0 + 517.0 * e6 / 5040.0) * (2.0 * xi_abs).sin()
+            + (23.0 * e4 / 360.0 + 251.0 * e6 / 3780.0) * (4.0 * xi_abs).sin()
+            + 761.0 * e6 / 45360.0 * (6.0 * xi_abs).sin();
+
+        // q(90°) - q(φ) = q(90°) (1 - sin ξ) = q(90°) cos² ξ / (1 + sin ξ)
+        let (sxi, cxi) = xi_abs.sin_cos();
+        let dq = self.qp * cxi * cxi / (1.0 + sxi);
+        for _ in 0..MAXIT {
+            let (sphi, cphi) = phi.sin_cos();
+            let w2 = 1.0 - e2 * sphi * sphi;
+            let dphi = (self.q_to_pole(sphi, cphi) - dq) * w2 * w2 / (2.0 * (1.0 - e2) * cphi);
+            phi = (phi + dphi).min(FRAC_PI_2);
+            if dphi.abs() <= f64::EPSILON {
+                break;
+            }
+        }
+
+        phi.copysign(xi)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        earth::ellipsoid::WGS84,
+        units::{convert::deg_to_rad, Deg, Meter},
+    };
+
+    const KINDS: [Latitude; 6] = [
+        Latitude::Geodetic,
+        Latitude::Reduced,
+        Latitude::Geocentric,
+        Latitude::Rectifying,
+        Latitude::Conformal,
+        Latitude::Authalic,
+    ];
+
+    #[test]
+    fn round_trip() {
+        let lats = AuxiliaryLatitudes::new(&WGS84).unwrap();
+        for deg in (-90..=90)
+            .map(|d| d as f64)
+            .chain([0.001, 45.5, 89.999, -89.9999])
+        {
+            let phi = deg_to_rad(Deg(deg));
+            for kind in KINDS {
+                let lat = lats.convert(phi, Latitude::Geodetic, kind).unwrap();
+                let back = lats.convert(lat, kind, Latitude::Geodetic).unwrap();
+                assert!((back.0 - phi.0).abs() < 2e-15, "{kind:?} at {deg}°");
+            }
+        }
+    }
+
+    #[test]
+    fn rectifying_meridian_arc() {
+        // The rectifying latitude in proportion to the meridian arc, found by
+        // integrating the meridional radius of curvature with Simpson's rule.
+        let lats = AuxiliaryLatitudes::new(&WGS84).unwrap();
+        let Meter(quarter) = WGS84.quarter_meridian();
+        let phi = deg_to_rad(Deg(52.0)).0;
+
+        let steps = 1000;
+        let h = phi / steps as f64;
+        let m = |i: usize| WGS84.meridional_r(Rad(i as f64 * h)).0 .0;
+        let arc = (1..steps).fold(m(0) + m(steps), |sum, i| {
+            sum + if i % 2 == 1 { 4.0 } else { 2.0 } * m(i)
+        }) * h
+            / 3.0;
+
+        let Rad(mu) = lats
+            .convert(Rad(phi), Latitude::Geodetic, Latitude::Rectifying)
+            .unwrap();
+        assert!((mu / FRAC_PI_2 * quarter - arc).abs() < 1e-6);
+    }
+
+    #[test]
+    fn sphere() {
+        let ellipsoid = Ellipsoid {
+            recip_f: f64::INFINITY,
+            ..WGS84
+        };
+        let lats = AuxiliaryLatitudes::new(&ellipsoid).unwrap();
+        let phi = deg_to_rad(Deg(30.0));
+        for kind in KINDS {
+            let lat = lats.convert(phi, Latitude::Geodetic, kind).unwrap();
+            assert!((lat.0 - phi.0).abs() < 1e-15, "{kind:?}");
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::units::{convert::*, DMS};
-use crate::units::{Deg, Rad};
+use crate::units::{Deg, Meter, Rad};
 use std::fmt;
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -33,3 +33,47 @@ impl fmt::Display for LatLng {
         write!(f, "({}, {})", lat, lng)
     }
 }
+
+/// A position with its height above the ellipsoid, along the normal.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct LatLngAlt {
+    pub lat: Rad,
+    pub lng: Rad,
+    pub alt: Meter,
+}
+
+impl LatLngAlt {
+    pub fn new(lat: f64, lng: f64, alt: f64) -> Self {
+        LatLngAlt {
+            lat: deg_to_rad(Deg(lat)),
+            lng: deg_to_rad(Deg(lng)),
+            alt: Meter(alt),
+        }
+    }
+
+    /// The position without its height.
+    pub fn latlng(&self) -> LatLng {
+        LatLng {
+            lat: self.lat,
+            lng: self.lng,
+        }
+    }
+}
+
+impl From<LatLng> for LatLngAlt {
+    fn from(LatLng { lat, lng }: LatLng) -> Self {
+        LatLngAlt {
+            lat,
+            lng,
+            alt: Meter(0.0),
+        }
+    }
+}
+
+impl fmt::Display for LatLngAlt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let lat = rad_to_deg(self.lat);
+        let lng = rad_to_deg(self.lng);
+        write!(f, "({}, {}, {}m)", lat, lng, self.alt.0)
+    }
+}
@@ -0,0 +1,293 @@
+// Local tangent plane frames at a reference point, with axes along the east,
+// the north and the normal to the ellipsoid there, the up. Positions are
+// rotated into these frames from their Earth-centred Earth-fixed coordinates
+// relative to the reference point, so that the frames are exact at any
+// distance and not only near the reference point.
+//
+// SEE: <https://en.wikipedia.org/wiki/Local_tangent_plane_coordinates>
+use crate::{
+    earth::ellipsoid::Ellipsoid,
+    units::{convert::rad_to_deg, Angle, Meter, Rad},
+};
+use std::fmt;
+
+use super::{
+    ecef::{from_ecef, to_ecef, Ecef},
+    error::GeodesyError,
+    latlng::LatLngAlt,
+    problems::{Az, Dist},
+};
+
+/// East-North-Up coordinates.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Enu {
+    pub e: Meter,
+    pub n: Meter,
+    pub u: Meter,
+}
+
+/// North-East-Down coordinates.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Ned {
+    pub n: Meter,
+    pub e: Meter,
+    pub d: Meter,
+}
+
+/// Azimuth-Elevation-Range coordinates, the azimuth normalized to 0° .. 360°
+/// and the elevation in -90° .. 90°, negative below the horizontal plane.
+#[derive(Debug, Clone, Copy)]
+pub struct Aer {
+    pub az: Az,
+    pub elev: Rad,
+    /// The slant range, the straight line distance.
+    pub range: Dist,
+}
+
+impl From<Enu> for Ned {
+    fn from(Enu { e, n, u }: Enu) -> Self {
+        Ned {
+            n,
+            e,
+            d: Meter(-u.0),
+        }
+    }
+}
+
+impl From<Ned> for Enu {
+    fn from(Ned { n, e, d }: Ned) -> Self {
+        Enu {
+            e,
+            n,
+            u: Meter(-d.0),
+        }
+    }
+}
+
+impl From<Enu> for Aer {
+    fn from(
+        Enu {
+            e: Meter(e),
+            n: Meter(n),
+            u: Meter(u),
+        }: Enu,
+    ) -> Self {
+        let horizontal = e.hypot(n);
+        Aer {
+            az: Az {
+                az: Rad(e.atan2(n)).normalize().0,
+            },
+            elev: Rad(u.atan2(horizontal)),
+            range: Dist {
+                dist: horizontal.hypot(u),
+            },
+        }
+    }
+}
+
+impl From<Aer> for Enu {
+    fn from(Aer { az, elev, range }: Aer) -> Self {
+        let (sin_az, cos_az) = az.az.sin_cos();
+        let (sin_elev, cos_elev) = elev.0.sin_cos();
+        let horizontal = range.dist * cos_elev;
+        Enu {
+            e: Meter(horizontal * sin_az),
+            n: Meter(horizontal * cos_az),
+            u: Meter(range.dist * sin_elev),
+        }
+    }
+}
+
+impl fmt::Display for Enu {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(E={}m, N={}m, U={}m)", self.e.0, self.n.0, self.u.0)
+    }
+}
+
+impl fmt::Display for Ned {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(N={}m, E={}m, D={}m)", self.n.0, self.e.0, self.d.0)
+    }
+}
+
+impl fmt::Display for Aer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let elev = rad_to_deg(self.elev);
+        write!(
+            f,
+            "(az={}, elev={:.2}, range={})",
+            self.az, elev, self.range
+        )
+    }
+}
+
+/// The local tangent plane at a reference point, for transforming any number
+/// of positions into and out of its frames.
+///
+/// ```
+/// # use auxillary_sphere::{
+/// #     earth::ellipsoid::WGS84,
+/// #     geodesy::{latlng::LatLngAlt, local::LocalFrame},
+/// #     units::convert::rad_to_deg,
+/// # };
+/// // The glide angle from 1500m to a turnpoint at 300m, 20km away.
+/// let glider = LatLngAlt::new(47.0, 8.0, 1500.0);
+/// let turnpoint = LatLngAlt::new(47.18, 8.0, 300.0);
+///
+/// let frame = LocalFrame::new(&WGS84, &glider).unwrap();
+/// let aer = frame.to_aer(&turnpoint).unwrap();
+/// let glide = -rad_to_deg(aer.elev).0;
+/// assert!(glide > 3.4 && glide < 3.6);
+/// ```
+#[derive(Clone, Copy)]
+pub struct LocalFrame {
+    ellipsoid: Ellipsoid,
+    origin: Ecef,
+    sin_lat: f64,
+    cos_lat: f64,
+    sin_lng: f64,
+    cos_lng: f64,
+}
+
+impl LocalFrame {
+    /// The frame at a reference point, with its latitude rejected outside the
+    /// range -90° .. 90°. At a pole, north is along the reference longitude.
+    pub fn new(ellipsoid: &Ellipsoid, origin: &LatLngAlt) -> Result<Self, GeodesyError> {
+        let ecef = to_ecef(ellipsoid, origin)?;
+        let (sin_lat, cos_lat) = origin.lat.0.sin_cos();
+        let (sin_lng, cos_lng) = origin.lng.0.sin_cos();
+
+        Ok(LocalFrame {
+            ellipsoid: *ellipsoid,
+            origin: ecef,
+            sin_lat,
+            cos_lat,
+            sin_lng,
+            cos_lng,
+        })
+    }
+
+    /// The East-North-Up coordinates of a position.
+    pub fn to_enu(&self, p: &LatLngAlt) -> Result<Enu, GeodesyError> {
+        let Ecef {
+            x: Meter(x),
+            y: Meter(y),
+            z: Meter(z),
+        } = to_ecef(&self.ellipsoid, p)?;
+        let dx = x - self.origin.x.0;
+        let dy = y - self.origin.y.0;
+        let dz = z - self.origin.z.0;
+
+        // Along the parallel and the meridian, (dx, dy) rotated by the
+        // longitude to t and then (t, dz) rotated by the latitude.
+        let t = self.cos_lng * dx + self.sin_lng * dy;
+        Ok(Enu {
+            e: Meter(-self.sin_lng * dx + self.cos_lng * dy),
+            n: Meter(-self.sin_lat * t + self.cos_lat * dz),
+            u: Meter(self.cos_lat * t + self.sin_lat * dz),
+        })
+    }
+
+    /// The position of East-North-Up coordinates.
+    pub fn from_enu(&self, enu: &Enu) -> Result<LatLngAlt, GeodesyError> {
+        let Enu {
+            e: Meter(e),
+            n: Meter(n),
+            u: Meter(u),
+        } = *enu;
+
+        let t = -self.sin_lat * n + self.cos_lat * u;
+        let ecef = Ecef {
+            x: Meter(self.origin.x.0 + self.cos_lng * t - self.sin_lng * e),
+            y: Meter(self.origin.y.0 + self.sin_lng * t + self.cos_lng * e),
+            z: Meter(self.origin.z.0 + self.cos_lat * n + self.sin_lat * u),
+        };
+        from_ecef(&self.ellipsoid, &ecef)
+    }
+
+    /// The North-East-Down coordinates of a position.
+    pub fn to_ned(&self, p: &LatLngAlt) -> Result<Ned, GeodesyError> {
+        self.to_enu(p).map(Ned::from)
+    }
+
+    /// The position of North-East-Down coordinates.
+    pub fn from_ned(&self, ned: &Ned) -> Result<LatLngAlt, GeodesyError> {
+        self.from_enu(&Enu::from(*ned))
+    }
+
+    /// The azimuth, elevation and slant range of a position, with the azimuth
+    /// taken as 0° directly above or below the reference point.
+    pub fn to_aer(&self, p: &LatLngAlt) -> Result<Aer, GeodesyError> {
+        self.to_enu(p).map(Aer::from)
+    }
+
+    /// The position at an azimuth, elevation and slant range.
+    pub fn from_aer(&self, aer: &Aer) -> Result<LatLngAlt, GeodesyError> {
+        self.from_enu(&Enu::from(*aer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        earth::ellipsoid::WGS84,
+        units::{convert::deg_to_rad, Deg},
+    };
+
+    #[test]
+    fn straight_up() {
+        let origin = LatLngAlt::new(-33.9, 151.2, 20.0);
+        let frame = LocalFrame::new(&WGS84, &origin).unwrap();
+        let enu = frame.to_enu(&LatLngAlt::new(-33.9, 151.2, 1020.0)).unwrap();
+
+        assert!(enu.e.0.abs() < 1e-9);
+        assert!(enu.n.0.abs() < 1e-9);
+        assert!((enu.u.0 - 1000.0).abs() < 1e-9);
+
+        let ned = Ned::from(enu);
+        assert!((ned.d.0 + 1000.0).abs() < 1e-9);
+
+        let aer = Aer::from(enu);
+        assert!((aer.elev.0 - deg_to_rad(Deg(90.0)).0).abs() < 1e-12);
+        assert!((aer.range.dist - 1000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn below_the_horizon() {
+        // A point at the same height 100km to the north is below the horizon
+        // by close to half the angle subtended at the centre of the Earth.
+        let origin = LatLngAlt::new(45.0, 10.0, 0.0);
+        let frame = LocalFrame::new(&WGS84, &origin).unwrap();
+        let aer = frame.to_aer(&LatLngAlt::new(45.9, 10.0, 0.0)).unwrap();
+
+        assert!(aer.az.az.sin().abs() < 1e-12 && aer.az.az.cos() > 0.0);
+        assert!((aer.elev.0 + deg_to_rad(Deg(0.45)).0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn round_trip() {
+        for origin in [
+            LatLngAlt::new(51.5, -0.1, 35.0),
+            LatLngAlt::new(90.0, 0.0, 0.0),
+            LatLngAlt::new(-12.0, 179.9, 3000.0),
+        ] {
+            let frame = LocalFrame::new(&WGS84, &origin).unwrap();
+            for p in [
+                LatLngAlt::new(51.6, -0.3, 1200.0),
+                LatLngAlt::new(-11.5, -179.8, 100.0),
+                LatLngAlt::new(0.0, 90.0, 35786e3),
+            ] {
+                let aer = frame.to_aer(&p).unwrap();
+                let q = frame.from_aer(&aer).unwrap();
+                assert!((q.lat.0 - p.lat.0).abs() < 1e-12);
+                assert!((q.lng.0 - p.lng.0).abs() < 1e-12);
+                assert!((q.alt.0 - p.alt.0).abs() < 1e-5);
+
+                let q = frame.from_ned(&frame.to_ned(&p).unwrap()).unwrap();
+                assert!((q.lat.0 - p.lat.0).abs() < 1e-12);
+                assert!((q.alt.0 - p.alt.0).abs() < 1e-5);
+            }
+        }
+    }
+}
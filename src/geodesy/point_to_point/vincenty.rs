@@ -1,27 +1,22 @@
-use std::f64::consts::PI;
+use std::f64::consts::{PI, TAU};
 
 use crate::{
-    earth::ellipsoid::{flattening, polar_r, Ellipsoid},
+    earth::ellipsoid::{flattening, is_valid, polar_r, Ellipsoid},
     geodesy::{
+        error::GeodesyError,
+        karney::geodesic_aux,
+        latitude::AuxiliaryLatitudes,
         latlng::LatLng,
         problems::{Az, Dist, InverseProblem, InverseSolution},
-        vincenty::GeodeticAccuracy,
+        vincenty::{check_lat, GeodeticAccuracy},
     },
-    units::{Meter, Rad, Radius},
+    units::{Angle, Meter, Rad, Radius},
 };
 
-#[derive(Debug, Clone, Copy)]
-enum GeodeticInverse {
-    Solution(InverseSolution),
-    Antipodal,
-    #[allow(dead_code)]
-    Abnormal,
-}
-
 #[derive(Debug, Clone, Copy)]
 struct InverseStep {
     tolerance: GeodeticAccuracy,
-    a: f64,
+    ep2: f64,
     b: f64,
     f: f64,
     l: f64,
@@ -31,19 +26,30 @@ struct InverseStep {
     cos_u2: f64,
     sin_u1_sin_u2: f64,
     cos_u1_cos_u2: f64,
+    // Whether to find the reduced length and scales, left out for distances.
+    with_aux: bool,
 }
 
-impl InverseStep {
-    fn iloop(&self, lambda: f64) -> GeodeticInverse {
-        if lambda.abs() > PI {
-            return GeodeticInverse::Antipodal;
-        }
-
-        let sin_lambda = lambda.sin();
-        let cos_lambda = lambda.cos();
+// The terms of a step of the iteration on λ that are needed again for the
+// solution.
+#[derive(Debug, Clone, Copy)]
+struct LambdaStep {
+    sin_lambda: f64,
+    cos_lambda: f64,
+    sin2_sigma: f64,
+    sin_sigma: f64,
+    cos_sigma: f64,
+    sigma: f64,
+    cos2_alpha: f64,
+    cos2_sigma_m: f64,
+}
 
-        let i_prime = self.cos_u1 * sin_lambda;
-        let j_prime = -self.sin_u1 * self.cos_u2 + self.cos_u1 * self.sin_u2 * cos_lambda;
+impl InverseStep {
+    // A single step of the iteration, returning the next λ and the terms of
+    // this step. Only the terms for λ are evaluated, leaving the distance and
+    // azimuths until the iteration has converged.
+    fn step(&self, lambda: f64) -> (f64, LambdaStep) {
+        let (sin_lambda, cos_lambda) = lambda.sin_cos();
 
         let i = self.cos_u2 * sin_lambda;
         let j = self.cos_u1 * self.sin_u2 - self.sin_u1 * self.cos_u2 * cos_lambda;
@@ -57,7 +63,6 @@ impl InverseStep {
         let sin_alpha = self.cos_u1_cos_u2 * sin_lambda / sin_sigma;
         let cos2_alpha = 1.0 - sin_alpha * sin_alpha;
         let c = self.f / 16.0 * cos2_alpha * (4.0 + self.f * (4.0 - 3.0 * cos2_alpha));
-        let u2 = cos2_alpha * (self.a * self.a - self.b * self.b) / (self.b * self.b);
 
         let cos2_sigma_m = if cos2_alpha == 0.0 {
             0.0
@@ -66,6 +71,45 @@ impl InverseStep {
         };
         let cos2_2_sigma_m = cos2_sigma_m * cos2_sigma_m;
 
+        let x = cos2_sigma_m + c * cos_sigma * (-1.0 + 2.0 * cos2_2_sigma_m);
+        let lambda_prime = self.l + (1.0 - c) * self.f * sin_alpha * (sigma + c * sin_sigma * x);
+
+        (
+            lambda_prime,
+            LambdaStep {
+                sin_lambda,
+                cos_lambda,
+                sin2_sigma,
+                sin_sigma,
+                cos_sigma,
+                sigma,
+                cos2_alpha,
+                cos2_sigma_m,
+            },
+        )
+    }
+
+    fn solution(&self, step: &LambdaStep, iterations: usize) -> InverseSolution {
+        let LambdaStep {
+            sin_lambda,
+            cos_lambda,
+            sin2_sigma,
+            sin_sigma,
+            cos_sigma,
+            sigma,
+            cos2_alpha,
+            cos2_sigma_m,
+        } = *step;
+
+        let i_prime = self.cos_u1 * sin_lambda;
+        let j_prime = -self.sin_u1 * self.cos_u2 + self.cos_u1 * self.sin_u2 * cos_lambda;
+
+        let i = self.cos_u2 * sin_lambda;
+        let j = self.cos_u1 * self.sin_u2 - self.sin_u1 * self.cos_u2 * cos_lambda;
+
+        let u2 = cos2_alpha * self.ep2;
+        let cos2_2_sigma_m = cos2_sigma_m * cos2_sigma_m;
+
         let a = 1.0 + u2 / 16384.0 * (4096.0 + u2 * (-768.0 + u2 * (320.0 - 175.0 * u2)));
         let b = u2 / 1024.0 * (256.0 + u2 * (-128.0 + u2 * (74.0 - 47.0 * u2)));
 
@@ -74,96 +118,606 @@ impl InverseStep {
 
         let delta_sigma = b * sin_sigma * (cos2_sigma_m + b / 4.0 * y);
 
-        let x = cos2_sigma_m + c * cos_sigma * (-1.0 + 2.0 * cos2_2_sigma_m);
-        let lambda_prime = self.l + (1.0 - c) * self.f * sin_alpha * (sigma + c * sin_sigma * x);
-
         let s = self.b * a * (sigma - delta_sigma);
-        let alpha1 = i.atan2(j);
-        let alpha2 = i_prime.atan2(j_prime);
+        let alpha1 = Rad(i.atan2(j)).normalize().0;
+        let alpha2 = Rad(i_prime.atan2(j_prime)).normalize().0;
 
-        if (lambda - lambda_prime).abs() >= self.tolerance.accuracy {
-            self.iloop(lambda_prime)
-        } else {
-            GeodeticInverse::Solution(InverseSolution {
-                s: Dist { dist: s },
-                az1: Az { az: alpha1 },
-                az2: Some(Az { az: alpha2 }),
-            })
+        InverseSolution {
+            s: Dist { dist: s },
+            az1: Az { az: alpha1 },
+            az2: Some(Az { az: alpha2 }),
+            iterations,
+            aux: self.with_aux.then(|| {
+                geodesic_aux(
+                    self.ep2,
+                    self.b,
+                    (self.sin_u1, self.cos_u1),
+                    alpha1.sin_cos(),
+                    sigma,
+                )
+            }),
         }
     }
+
+    fn iloop(&self, lambda: f64) -> Result<InverseSolution, GeodesyError> {
+        let mut lambda = lambda;
+
+        for i in 1..=self.tolerance.max_iterations {
+            if lambda.abs() > PI {
+                return Err(GeodesyError::Antipodal);
+            }
+
+            let (lambda_prime, step) = self.step(lambda);
+            if (lambda - lambda_prime).abs() < self.tolerance.accuracy {
+                return Ok(self.solution(&step, i));
+            }
+
+            lambda = lambda_prime;
+        }
+
+        Err(GeodesyError::IterationLimit(self.tolerance.max_iterations))
+    }
 }
 
-fn normalize_lng(lng: f64) -> f64 {
-    lng % (2.0 * PI)
+// The geodesic leaving P₁ at azimuth α₁ up to where it first reaches the
+// latitude of P₂.
+#[derive(Debug, Clone, Copy)]
+struct AntipodalArc {
+    // The difference in longitude on the ellipsoid, L.
+    l: f64,
+    // The angular distance, σ, and twice the angular distance to its midpoint,
+    // 2σm, on the auxiliary sphere.
+    sigma: f64,
+    sigma_m2: f64,
+    sin_alpha0: f64,
+    cos_alpha2_cos_u2: f64,
 }
 
-fn inverse(
-    ellipsoid: Ellipsoid,
+// For nearly antipodal points, Vincenty's iteration on λ fails to converge.
+// Instead, with the points arranged so that U₁ <= 0, |U₂| <= |U₁| and L >= 0,
+// find the azimuth α₁ in 0 .. π of the geodesic that reaches the latitude of P₂
+// at its longitude. Following Karney, the longitude reached is monotonic in α₁
+// and we can solve for it on the auxiliary sphere with secant steps, a discrete
+// Newton's method, safeguarded by bisection.
+#[derive(Debug, Clone, Copy)]
+struct AntipodalStep {
     tolerance: GeodeticAccuracy,
-    p: InverseProblem,
-) -> GeodeticInverse {
-    let Rad(phi1) = p.x.lat;
-    let Rad(l1) = p.x.lng;
-    let Rad(phi2) = p.y.lat;
-    let Rad(l2) = p.y.lng;
-
-    let Radius(Meter(a)) = ellipsoid.equatorial_r;
-    let Radius(Meter(b)) = polar_r(&ellipsoid);
-    let f = flattening(&ellipsoid);
-
-    let aux_lat = |phi: f64| ((1.0 - f) * phi.tan()).atan();
-    let u1 = aux_lat(phi1);
-    let u2 = aux_lat(phi2);
-
-    let l = {
-        let l_prime = l2 - l1;
-        if l_prime.abs() <= PI {
-            l_prime
+    ep2: f64,
+    b: f64,
+    f: f64,
+    l: f64,
+    sin_u1: f64,
+    sin_u2: f64,
+    cos_u1: f64,
+    cos_u2: f64,
+}
+
+impl AntipodalStep {
+    fn arc(&self, alpha1: f64) -> AntipodalArc {
+        let sin_alpha1 = alpha1.sin();
+        let cos_alpha1 = alpha1.cos();
+        let sin_alpha0 = sin_alpha1 * self.cos_u1;
+        let cos2_alpha0 = 1.0 - sin_alpha0 * sin_alpha0;
+
+        let sigma1 = self.sin_u1.atan2(cos_alpha1 * self.cos_u1);
+        let omega1 = (sin_alpha0 * self.sin_u1).atan2(cos_alpha1 * self.cos_u1);
+
+        let (sigma, omega, cos_alpha2_cos_u2) = if self.sin_u1 == 0.0 && self.sin_u2 == 0.0 {
+            // Leaving the equator, the geodesic next returns to it at σ = π.
+            (PI, PI, -cos_alpha1 * self.cos_u1)
         } else {
-            normalize_lng(l2) - normalize_lng(l1)
+            let x = cos_alpha1 * self.cos_u1;
+            let c = (x * x + (self.cos_u2 - self.cos_u1) * (self.cos_u2 + self.cos_u1))
+                .max(0.0)
+                .sqrt();
+            let sigma2 = self.sin_u2.atan2(c);
+            let omega2 = (sin_alpha0 * self.sin_u2).atan2(c);
+            let sigma = sigma2 - sigma1;
+            (
+                if sigma < 0.0 { sigma + 2.0 * PI } else { sigma },
+                omega2 - omega1,
+                c,
+            )
+        };
+
+        let sigma_m2 = 2.0 * sigma1 + sigma;
+        let cos2_sigma_m = sigma_m2.cos();
+        let c = self.f / 16.0 * cos2_alpha0 * (4.0 + self.f * (4.0 - 3.0 * cos2_alpha0));
+        let x = cos2_sigma_m + c * sigma.cos() * (-1.0 + 2.0 * cos2_sigma_m * cos2_sigma_m);
+        let l = omega - (1.0 - c) * self.f * sin_alpha0 * (sigma + c * sigma.sin() * x);
+
+        AntipodalArc {
+            l,
+            sigma,
+            sigma_m2,
+            sin_alpha0,
+            cos_alpha2_cos_u2,
         }
-    };
+    }
+
+    fn solution(&self, alpha1: f64, arc: &AntipodalArc, iterations: usize) -> InverseSolution {
+        let AntipodalArc {
+            sigma,
+            sigma_m2,
+            sin_alpha0,
+            cos_alpha2_cos_u2,
+            ..
+        } = *arc;
+
+        let cos2_alpha0 = 1.0 - sin_alpha0 * sin_alpha0;
+        let u2 = cos2_alpha0 * self.ep2;
+        let a = 1.0 + u2 / 16384.0 * (4096.0 + u2 * (-768.0 + u2 * (320.0 - 175.0 * u2)));
+        let b = u2 / 1024.0 * (256.0 + u2 * (-128.0 + u2 * (74.0 - 47.0 * u2)));
+
+        let sin_sigma = sigma.sin();
+        let cos_sigma = sigma.cos();
+        let cos2_sigma_m = sigma_m2.cos();
+        let cos2_2_sigma_m = cos2_sigma_m * cos2_sigma_m;
+
+        let y = cos_sigma * (-1.0 + 2.0 * cos2_2_sigma_m)
+            - b / 6.0
+                * cos2_sigma_m
+                * (-3.0 + 4.0 * sin_sigma * sin_sigma)
+                * (-3.0 + 4.0 * cos2_2_sigma_m);
+        let delta_sigma = b * sin_sigma * (cos2_sigma_m + b / 4.0 * y);
+
+        InverseSolution {
+            s: Dist {
+                dist: self.b * a * (sigma - delta_sigma),
+            },
+            az1: Az { az: alpha1 },
+            az2: Some(Az {
+                az: sin_alpha0.atan2(cos_alpha2_cos_u2),
+            }),
+            iterations,
+            aux: Some(geodesic_aux(
+                self.ep2,
+                self.b,
+                (self.sin_u1, self.cos_u1),
+                alpha1.sin_cos(),
+                sigma,
+            )),
+        }
+    }
+
+    fn iloop(&self) -> Result<InverseSolution, GeodesyError> {
+        // Between the ends of the bracket, the longitude reached crosses L.
+        let (mut lo, mut hi) = if self.sin_u1 == 0.0 && self.sin_u2 == 0.0 {
+            (0.0, PI / 2.0)
+        } else {
+            (0.0, PI)
+        };
+        let lo_sign = (self.arc(lo).l - self.l).signum();
+        let mut alpha1 = (lo + hi) / 2.0;
+        let mut prev: Option<(f64, f64)> = None;
+
+        for i in 1..=self.tolerance.max_iterations {
+            let arc = self.arc(alpha1);
+            let dl = arc.l - self.l;
+            if dl.abs() < self.tolerance.accuracy || hi - lo < self.tolerance.accuracy {
+                return Ok(self.solution(alpha1, &arc, i));
+            }
+
+            if dl.signum() == lo_sign {
+                lo = alpha1;
+            } else {
+                hi = alpha1;
+            }
 
-    let sin_u1 = u1.sin();
-    let sin_u2 = u2.sin();
-    let cos_u1 = u1.cos();
-    let cos_u2 = u2.cos();
-    let sin_u1_sin_u2 = sin_u1 * sin_u2;
-    let cos_u1_cos_u2 = cos_u1 * cos_u2;
+            // Take the secant step unless it leaves the bracket or the last
+            // step failed to at least halve the error.
+            let secant = match prev {
+                Some((alpha0, dl0)) if dl.abs() <= dl0.abs() / 2.0 => {
+                    alpha1 - dl * (alpha1 - alpha0) / (dl - dl0)
+                }
+                _ => f64::NAN,
+            };
 
-    let step = InverseStep {
-        tolerance,
-        a,
+            prev = Some((alpha1, dl));
+            alpha1 = if secant > lo && secant < hi {
+                secant
+            } else {
+                (lo + hi) / 2.0
+            };
+        }
+
+        Err(GeodesyError::IterationLimit(self.tolerance.max_iterations))
+    }
+}
+
+// Rearranges the points of a nearly antipodal inverse problem to solve with an
+// AntipodalStep and then restores the azimuths of the solution to the original
+// arrangement. The reduced latitudes are given as (sin U, cos U).
+fn inverse_antipodal(
+    tolerance: &GeodeticAccuracy,
+    (ep2, b, f): (f64, f64, f64),
+    (u1, u2): ((f64, f64), (f64, f64)),
+    l: f64,
+) -> Result<InverseSolution, GeodesyError> {
+    let given_u1 = u1;
+    let swap = u1.0.abs() < u2.0.abs();
+    let (u1, u2, l) = if swap { (u2, u1, -l) } else { (u1, u2, l) };
+    let flip_lat = u1.0 > 0.0;
+    let (u1, u2) = if flip_lat {
+        ((-u1.0, u1.1), (-u2.0, u2.1))
+    } else {
+        (u1, u2)
+    };
+    let flip_lng = l < 0.0;
+    let l = l.abs();
+
+    let step = AntipodalStep {
+        tolerance: *tolerance,
+        ep2,
         b,
         f,
         l,
-        sin_u1,
-        sin_u2,
-        cos_u1,
-        cos_u2,
-        sin_u1_sin_u2,
-        cos_u1_cos_u2,
+        sin_u1: u1.0,
+        sin_u2: u2.0,
+        cos_u1: u1.1,
+        cos_u2: u2.1,
     };
 
-    step.iloop(l)
+    let solution = step.iloop()?;
+    let mut alpha1 = solution.az1.az;
+    let mut alpha2 = solution.az2.map_or(0.0, |az| az.az);
+    if flip_lng {
+        (alpha1, alpha2) = (-alpha1, -alpha2);
+    }
+    if flip_lat {
+        (alpha1, alpha2) = (PI - alpha1, PI - alpha2);
+    }
+    if swap {
+        (alpha1, alpha2) = (alpha2 + PI, alpha1 + PI);
+    }
+
+    // The arc and reduced length are the same in any arrangement but not the
+    // scales and equatorial azimuth, so these are found again from P₁ as given.
+    let aux = solution
+        .aux
+        .map(|aux| geodesic_aux(ep2, b, given_u1, alpha1.sin_cos(), aux.sigma12.0));
+
+    Ok(InverseSolution {
+        az1: Az {
+            az: Rad(alpha1).normalize().0,
+        },
+        az2: Some(Az {
+            az: Rad(alpha2).normalize().0,
+        }),
+        aux,
+        ..solution
+    })
+}
+
+/// The constants of an ellipsoid, derived once, for solving any number of
+/// inverse problems with Vincenty's method. The cost of each problem is mostly
+/// in the iteration, so a problem costs about as much as with [inverse], alone
+/// or in [InverseContext::inverse_pairs]. Where only the distances are wanted,
+/// [InverseContext::distance_pairs] leaves out the reduced length and scales,
+/// saving about a quarter of the cost, as measured by
+/// `cargo bench --bench inverse`.
+///
+/// ```
+/// # use auxillary_sphere::{
+/// #     earth::ellipsoid::WGS84,
+/// #     geodesy::{latlng::LatLng, point_to_point::vincenty::InverseContext},
+/// # };
+/// let context = InverseContext::new(&WGS84, Default::default()).unwrap();
+/// let london = LatLng::new(51.5007, -0.1246);
+/// let pairs = [
+///     (london, LatLng::new(40.6892, -74.0445)),
+///     (london, LatLng::new(48.8584, 2.2945)),
+/// ];
+///
+/// let spans: Vec<f64> = context
+///     .inverse_pairs(&pairs)
+///     .into_iter()
+///     .map(|soln| soln.unwrap().s.dist)
+///     .collect();
+/// assert!(spans[1] < spans[0]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct InverseContext {
+    accuracy: GeodeticAccuracy,
+    b: f64,
+    f: f64,
+    // The reduced latitude, U, is given by tan U = (1 - ƒ) tan φ.
+    lats: AuxiliaryLatitudes,
+    // The second eccentricity squared, (a² - b²) / b².
+    ep2: f64,
 }
 
-fn distance_unchecked(ellipsoid: Ellipsoid, prob: InverseProblem) -> GeodeticInverse {
-    if prob.x == prob.y {
-        GeodeticInverse::Solution(InverseSolution {
-            s: Dist { dist: 0.0 },
-            az1: Az { az: 0.0 },
-            az2: Some(Az { az: PI }),
+impl InverseContext {
+    pub fn new(ellipsoid: &Ellipsoid, accuracy: GeodeticAccuracy) -> Result<Self, GeodesyError> {
+        if !is_valid(ellipsoid) {
+            return Err(GeodesyError::InvalidEllipsoid);
+        }
+
+        let Radius(Meter(b)) = polar_r(ellipsoid);
+        let f = flattening(ellipsoid);
+
+        Ok(InverseContext {
+            accuracy,
+            b,
+            f,
+            lats: AuxiliaryLatitudes::new(ellipsoid)?,
+            ep2: ellipsoid.second_eccentricity_sq(),
         })
+    }
+
+    fn reduce(&self, x: &LatLng) -> Result<Reduced, GeodesyError> {
+        let lat = check_lat(x.lat)?;
+        Ok(Reduced {
+            lat,
+            lng: x.lng.0,
+            u: self.lats.reduced(lat.0),
+        })
+    }
+
+    // The point reduced, reusing the last reduction if the point is the same,
+    // as along a path or from one point to many.
+    fn reduce_again(
+        &self,
+        x: &LatLng,
+        last: &mut Option<(LatLng, Reduced)>,
+    ) -> Result<Reduced, GeodesyError> {
+        match *last {
+            Some((y, reduced)) if y == *x => Ok(reduced),
+            _ => {
+                let reduced = self.reduce(x)?;
+                *last = Some((*x, reduced));
+                Ok(reduced)
+            }
+        }
+    }
+
+    fn inverse_step(
+        &self,
+        x: &Reduced,
+        y: &Reduced,
+        l: f64,
+        with_aux: bool,
+    ) -> Result<InverseSolution, GeodesyError> {
+        let (sin_u1, cos_u1) = x.u;
+        let (sin_u2, cos_u2) = y.u;
+
+        let step = InverseStep {
+            tolerance: self.accuracy,
+            ep2: self.ep2,
+            b: self.b,
+            f: self.f,
+            l,
+            sin_u1,
+            sin_u2,
+            cos_u1,
+            cos_u2,
+            sin_u1_sin_u2: sin_u1 * sin_u2,
+            cos_u1_cos_u2: cos_u1 * cos_u2,
+            with_aux,
+        };
+
+        match step.iloop(l) {
+            Err(GeodesyError::Antipodal | GeodesyError::IterationLimit(_)) => inverse_antipodal(
+                &self.accuracy,
+                (self.ep2, self.b, self.f),
+                ((sin_u1, cos_u1), (sin_u2, cos_u2)),
+                l,
+            ),
+            result => result,
+        }
+    }
+
+    fn inverse_reduced(
+        &self,
+        x: &Reduced,
+        y: &Reduced,
+        with_aux: bool,
+    ) -> Result<InverseSolution, GeodesyError> {
+        let l = lng_diff(x.lng, y.lng);
+
+        // Coincident points, with longitudes compared after normalizing so that
+        // -180° and 180° are the same meridian. As with Karney's solution, the
+        // azimuth at the end is that at the start, and the quantities of the
+        // geodesic are those of the zero length arc leaving at that azimuth.
+        if x.lat == y.lat && l == 0.0 {
+            let az1 = Az { az: 0.0 };
+            Ok(InverseSolution {
+                s: Dist { dist: 0.0 },
+                az1,
+                az2: Some(az1),
+                iterations: 0,
+                aux: with_aux.then(|| geodesic_aux(self.ep2, self.b, x.u, az1.az.sin_cos(), 0.0)),
+            })
+        } else {
+            self.inverse_step(x, y, l, with_aux)
+        }
+    }
+
+    /// The solution to the inverse geodesy problem, as with [inverse].
+    pub fn inverse(&self, prob: &InverseProblem) -> Result<InverseSolution, GeodesyError> {
+        self.inverse_reduced(&self.reduce(&prob.x)?, &self.reduce(&prob.y)?, true)
+    }
+
+    // Solves each pair in turn, reducing the latitude of a point once while it
+    // is repeated from one pair to the next.
+    fn solve_pairs<'a>(
+        &'a self,
+        pairs: &'a [(LatLng, LatLng)],
+        with_aux: bool,
+    ) -> impl Iterator<Item = Result<InverseSolution, GeodesyError>> + 'a {
+        let (mut last_x, mut last_y) = (None, None);
+        pairs.iter().map(move |(x, y)| {
+            let x = self.reduce_again(x, &mut last_x)?;
+            let y = self.reduce_again(y, &mut last_y)?;
+            self.inverse_reduced(&x, &y, with_aux)
+        })
+    }
+
+    /// Solves the inverse problem for each pair of points in turn, reducing the
+    /// latitude of a point once while it is repeated from one pair to the next,
+    /// as along a path or from one point to many.
+    pub fn inverse_pairs(
+        &self,
+        pairs: &[(LatLng, LatLng)],
+    ) -> Vec<Result<InverseSolution, GeodesyError>> {
+        self.solve_pairs(pairs, true).collect()
+    }
+
+    /// The distance between each pair of points in turn, as with
+    /// [InverseContext::inverse_pairs] but leaving out the reduced length and
+    /// geodesic scales, about a quarter of the cost of each problem.
+    pub fn distance_pairs(&self, pairs: &[(LatLng, LatLng)]) -> Vec<Result<Dist, GeodesyError>> {
+        self.solve_pairs(pairs, false)
+            .map(|soln| soln.map(|soln| soln.s))
+            .collect()
+    }
+}
+
+// A point with its latitude checked and reduced, as (sin U, cos U).
+#[derive(Debug, Clone, Copy)]
+struct Reduced {
+    lat: Rad,
+    lng: f64,
+    u: (f64, f64),
+}
+
+// The difference in longitude from x to y, in radians, normalized to -π .. π
+// keeping the sign of ±π as plus_minus_pi_rad does.
+fn lng_diff(x: f64, y: f64) -> f64 {
+    let l = (y - x) % TAU;
+    if l > PI {
+        l - TAU
+    } else if l < -PI {
+        l + TAU
     } else {
-        inverse(ellipsoid, GeodeticAccuracy { accuracy: 1e-12 }, prob)
+        l
     }
 }
 
-pub(crate) fn distance(e: Ellipsoid, x: LatLng, y: LatLng) -> Result<Dist, String> {
+/// The solution to the inverse geodesy problem, the distance between two
+/// points and the azimuths of the geodesic at each of them, both normalized to
+/// 0° .. 360°. As with Vincenty's α₂, the azimuth at the second point is in the
+/// direction P₁ P₂ produced. For many problems on the same ellipsoid, an
+/// [InverseContext] holds its constants.
+pub fn inverse(
+    ellipsoid: &Ellipsoid,
+    accuracy: &GeodeticAccuracy,
+    prob: &InverseProblem,
+) -> Result<InverseSolution, GeodesyError> {
+    InverseContext::new(ellipsoid, *accuracy)?.inverse(prob)
+}
+
+pub(crate) fn distance(e: Ellipsoid, x: LatLng, y: LatLng) -> Result<Dist, GeodesyError> {
     let prob = InverseProblem { x, y };
-    match distance_unchecked(e, prob) {
-        GeodeticInverse::Solution(solution) => Ok(solution.s),
-        _ => Err("Distance calculation failed".to_string()),
+    inverse(&e, &GeodeticAccuracy::default(), &prob).map(|solution| solution.s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{earth::ellipsoid::WGS84, units::convert::rad_to_deg};
+
+    #[test]
+    fn nearly_antipodal() {
+        // The example of Karney's Algorithms for geodesics, 2013, table 3.
+        let prob = InverseProblem {
+            x: LatLng::new(-30.0, 0.0),
+            y: LatLng::new(29.9, 179.8),
+        };
+        let InverseSolution {
+            s, az1, az2, aux, ..
+        } = inverse(&WGS84, &GeodeticAccuracy::default(), &prob).unwrap();
+        let aux = aux.unwrap();
+
+        assert!((s.dist - 19989832.827610).abs() < 0.0001);
+        assert!((rad_to_deg(Rad(az1.az)).0 - 161.890524736).abs() < 1e-7);
+        assert!((rad_to_deg(Rad(az2.unwrap().az)).0 - 18.090737246).abs() < 1e-7);
+        assert!((rad_to_deg(Rad(aux.alpha0.az)).0 - 15.629479665).abs() < 1e-7);
+        assert!((rad_to_deg(aux.sigma12).0 - 179.894971388).abs() < 1e-7);
+    }
+
+    #[test]
+    fn across_antimeridian() {
+        let x = LatLng::new(10.0, -170.0);
+        let y = LatLng::new(10.0, 170.0);
+        let east = inverse(
+            &WGS84,
+            &GeodeticAccuracy::default(),
+            &InverseProblem { x, y },
+        );
+        let west = inverse(
+            &WGS84,
+            &GeodeticAccuracy::default(),
+            &InverseProblem { x: y, y: x },
+        );
+
+        assert_eq!(east.unwrap().s.dist, west.unwrap().s.dist);
+        assert!(east.unwrap().s.dist < 2200000.0);
+    }
+
+    #[test]
+    fn pairs_as_single_problems() {
+        // Along a path, from one point to many and with a bad latitude between.
+        let context = InverseContext::new(&WGS84, GeodeticAccuracy::default()).unwrap();
+        let london = LatLng::new(51.5007, -0.1246);
+        let paris = LatLng::new(48.8584, 2.2945);
+        let rome = LatLng::new(41.8902, 12.4922);
+        let pairs = [
+            (london, paris),
+            (paris, rome),
+            (rome, rome),
+            (london, LatLng::new(95.0, 0.0)),
+            (london, rome),
+        ];
+
+        let solns = context.inverse_pairs(&pairs);
+        let dists = context.distance_pairs(&pairs);
+        for (i, &(x, y)) in pairs.iter().enumerate() {
+            match context.inverse(&InverseProblem { x, y }) {
+                Ok(soln) => {
+                    let batch = solns[i].unwrap();
+                    assert_eq!(batch.s.dist, soln.s.dist);
+                    assert_eq!(batch.az1.az, soln.az1.az);
+                    assert_eq!(batch.aux.unwrap().m12.dist, soln.aux.unwrap().m12.dist);
+                    assert_eq!(dists[i].unwrap().dist, soln.s.dist);
+                }
+                Err(e) => {
+                    assert_eq!(solns[i].unwrap_err(), e);
+                    assert_eq!(dists[i].unwrap_err(), e);
+                }
+            }
+        }
+        assert!(solns[3].is_err());
+    }
+
+    #[test]
+    fn coincident() {
+        // The same point either side of the antimeridian.
+        let x = LatLng::new(0.0, 180.0);
+        let y = LatLng::new(0.0, -180.0);
+        let InverseSolution {
+            s,
+            az1,
+            az2,
+            iterations,
+            aux,
+        } = inverse(
+            &WGS84,
+            &GeodeticAccuracy::default(),
+            &InverseProblem { x, y },
+        )
+        .unwrap();
+        let aux = aux.unwrap();
+
+        assert_eq!(s.dist, 0.0);
+        assert_eq!(iterations, 0);
+        assert_eq!(az2.unwrap().az, az1.az);
+
+        // Clairaut's constant of the arc leaving at az1 on the equator, with
+        // no length and unit scales.
+        assert!((aux.alpha0.az.sin() - az1.az.sin()).abs() < 1e-15);
+        assert!((aux.alpha0.az.cos() - az1.az.cos()).abs() < 1e-15);
+        assert_eq!(aux.m12.dist, 0.0);
+        assert_eq!((aux.scale12, aux.scale21), (1.0, 1.0));
     }
 }
@@ -0,0 +1,196 @@
+use crate::{earth::ellipsoid::Ellipsoid, units::convert::rad_to_deg};
+use std::f64::consts::PI;
+
+use super::{
+    error::GeodesyError,
+    karney::{ang_diff, ang_normalize, sum_error, Geodesic},
+    latlng::LatLng,
+    problems::Dist,
+    vincenty::check_lat,
+};
+
+// The area of a polygon with geodesic edges, summing for each edge the area
+// S₁₂ between the edge and the equator, as in GeographicLib's PolygonArea.
+//
+// Algorithms for geodesics
+// Journal of Geodesy 87, 43–55 (2013)
+// C. F. F. Karney, <https://doi.org/10.1007/s00190-012-0578-z>
+
+/// The area and perimeter of a polygon with geodesic edges.
+#[derive(Debug, Clone, Copy)]
+pub struct PolygonArea {
+    /// The area in square metres, positive when the vertices go around it
+    /// counter-clockwise and in the range -A/2 .. A/2 where A is the area of
+    /// the ellipsoid.
+    pub area: f64,
+    pub perimeter: Dist,
+}
+
+// A sum with the roundoff error carried along.
+#[derive(Debug, Default, Clone, Copy)]
+struct Accumulator {
+    s: f64,
+    t: f64,
+}
+
+impl Accumulator {
+    fn add(&mut self, y: f64) {
+        let (z, u) = sum_error(y, self.t);
+        let (s, t) = sum_error(z, self.s);
+        self.s = s;
+        self.t = t;
+        if self.s == 0.0 {
+            self.s = u;
+        } else {
+            self.t += u;
+        }
+    }
+
+    fn sum(&self) -> f64 {
+        self.s + self.t
+    }
+}
+
+// Whether the edge crosses the prime meridian going east, 1, or west, -1,
+// counting the points on the meridian as being east of it.
+fn transit(lng1: f64, lng2: f64) -> i32 {
+    let (lng12, _) = ang_diff(lng1, lng2);
+    let lng1 = ang_normalize(lng1);
+    let lng2 = ang_normalize(lng2);
+    if lng12 > 0.0 && ((lng1 < 0.0 && lng2 >= 0.0) || (lng1 > 0.0 && lng2 == 0.0)) {
+        1
+    } else if lng12 < 0.0 && lng1 >= 0.0 && lng2 < 0.0 {
+        -1
+    } else {
+        0
+    }
+}
+
+impl Geodesic {
+    /// The area and perimeter of the polygon with geodesic edges joining the
+    /// vertices of the ring in turn and the last back to the first. The ring
+    /// may enclose a pole or cross the antimeridian.
+    pub fn polygon_area(&self, ring: &[LatLng]) -> Result<PolygonArea, GeodesyError> {
+        let mut vertices = Vec::with_capacity(ring.len());
+        for x in ring {
+            let lat = rad_to_deg(check_lat(x.lat)?).0;
+            vertices.push((lat, rad_to_deg(x.lng).0));
+        }
+
+        let mut perimeter = Accumulator::default();
+        let mut area = Accumulator::default();
+        let mut crossings = 0;
+
+        let edges = vertices.iter().zip(vertices.iter().cycle().skip(1));
+        for (&(lat1, lng1), &(lat2, lng2)) in edges {
+            let inv = self.gen_inverse(lat1, lng1, lat2, lng2, true);
+            perimeter.add(inv.s12);
+            area.add(inv.area.unwrap_or_default());
+            crossings += transit(lng1, lng2);
+        }
+
+        // The sum of S₁₂ over the edges is the area of the polygon to the
+        // right of the edges, taken modulo the area of the ellipsoid, less
+        // half of that when the ring encloses a pole.
+        let total = 4.0 * PI * self.c2();
+        let mut a = area.sum() % total;
+        if crossings % 2 != 0 {
+            a += if a < 0.0 { total / 2.0 } else { -total / 2.0 };
+        }
+
+        // Counter-clockwise positive, in the range -A/2 .. A/2.
+        let mut a = -a;
+        if a > total / 2.0 {
+            a -= total;
+        } else if a <= -total / 2.0 {
+            a += total;
+        }
+
+        Ok(PolygonArea {
+            area: a + 0.0,
+            perimeter: Dist {
+                dist: perimeter.sum(),
+            },
+        })
+    }
+}
+
+/// The area and perimeter of the polygon on the ellipsoid, as with
+/// [Geodesic::polygon_area].
+///
+/// ```
+/// # use auxillary_sphere::{
+/// #     earth::ellipsoid::WGS84,
+/// #     geodesy::{latlng::LatLng, polygon},
+/// # };
+/// let ring = [
+///     LatLng::new(0.0, -1.0),
+///     LatLng::new(-1.0, 0.0),
+///     LatLng::new(0.0, 1.0),
+///     LatLng::new(1.0, 0.0),
+/// ];
+/// let p = polygon::area(&WGS84, &ring).unwrap();
+///
+/// assert!((p.area - 24619419146.0).abs() < 1.0);
+/// assert!((p.perimeter.dist - 627598.2731).abs() < 1e-4);
+/// ```
+pub fn area(ellipsoid: &Ellipsoid, ring: &[LatLng]) -> Result<PolygonArea, GeodesyError> {
+    Geodesic::new(ellipsoid)?.polygon_area(ring)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::earth::ellipsoid::WGS84;
+
+    fn ring(vertices: &[(f64, f64)]) -> Vec<LatLng> {
+        vertices
+            .iter()
+            .map(|&(lat, lng)| LatLng::new(lat, lng))
+            .collect()
+    }
+
+    #[test]
+    fn around_poles() {
+        // From the tests of GeographicLib's Planimeter.
+        let north = ring(&[(89.0, 0.0), (89.0, 90.0), (89.0, 180.0), (89.0, 270.0)]);
+        let p = area(&WGS84, &north).unwrap();
+        assert!((p.perimeter.dist - 631819.8745).abs() < 1e-4);
+        assert!((p.area - 24952305678.0).abs() < 1.0);
+
+        let south = ring(&[(-89.0, 0.0), (-89.0, 90.0), (-89.0, 180.0), (-89.0, 270.0)]);
+        let p = area(&WGS84, &south).unwrap();
+        assert!((p.perimeter.dist - 631819.8745).abs() < 1e-4);
+        assert!((p.area + 24952305678.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn octant() {
+        let octant = ring(&[(90.0, 0.0), (0.0, 0.0), (0.0, 90.0)]);
+        let p = area(&WGS84, &octant).unwrap();
+        assert!((p.perimeter.dist - 30022685.0).abs() < 1.0);
+        assert!((p.area - 63758202715511.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn antimeridian() {
+        let square = |lng: f64| {
+            ring(&[
+                (-1.0, lng - 1.0),
+                (-1.0, lng + 1.0),
+                (1.0, lng + 1.0),
+                (1.0, lng - 1.0),
+            ])
+        };
+        let at_greenwich = area(&WGS84, &square(0.0)).unwrap();
+        let at_antimeridian = area(&WGS84, &square(180.0)).unwrap();
+        assert!(at_greenwich.area > 0.0);
+        assert!((at_greenwich.area - at_antimeridian.area).abs() < 1e-3);
+        assert!((at_greenwich.perimeter.dist - at_antimeridian.perimeter.dist).abs() < 1e-6);
+
+        let mut reversed = square(180.0);
+        reversed.reverse();
+        let p = area(&WGS84, &reversed).unwrap();
+        assert!((p.area + at_antimeridian.area).abs() < 1e-3);
+    }
+}
@@ -27,10 +27,31 @@ pub struct InverseProblem {
     pub y: LatLng,
 }
 
+/// The quantities of the geodesic of a solution other than its ends, length
+/// and azimuths there.
+#[derive(Debug, Clone, Copy)]
+pub struct GeodesicAux {
+    /// The arc length σ₁₂ on the auxiliary sphere.
+    pub sigma12: Rad,
+    /// The azimuth α₀ where the geodesic crosses the equator going north,
+    /// normalized to 0° .. 360°, with Clairaut's constant sin α₀ = cos β sin α.
+    pub alpha0: Az,
+    /// The reduced length m₁₂.
+    pub m12: Dist,
+    /// The geodesic scale M₁₂ of the second point relative to the first.
+    pub scale12: f64,
+    /// The geodesic scale M₂₁ of the first point relative to the second.
+    pub scale21: f64,
+}
+
 #[derive(Debug)]
 pub struct DirectSolution {
     pub y: LatLng,
     pub az2: Option<Az>,
+    /// The number of iterations taken, zero for a closed form solution.
+    pub iterations: usize,
+    /// None for solutions along lines that aren't geodesics.
+    pub aux: Option<GeodesicAux>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -38,6 +59,70 @@ pub struct InverseSolution {
     pub s: Dist,
     pub az1: Az,
     pub az2: Option<Az>,
+    /// The number of iterations taken, zero for a closed form solution.
+    pub iterations: usize,
+    /// None for solutions along lines that aren't geodesics.
+    pub aux: Option<GeodesicAux>,
+}
+
+/// A point and the geodesic through two others, the track, that it is off.
+#[derive(Debug)]
+pub struct TrackProblem {
+    pub x: LatLng,
+    pub y: LatLng,
+    pub p: LatLng,
+}
+
+#[derive(Debug)]
+pub struct TrackSolution {
+    /// The distance from the track, positive when the point is to the right of
+    /// the track going from x towards y.
+    pub cross_track: Dist,
+    /// The distance along the track from x to the closest point, negative when
+    /// behind x.
+    pub along_track: Dist,
+    /// The point on the track closest to the point off it.
+    pub closest: LatLng,
+    /// The number of iterations taken, zero for a closed form solution.
+    pub iterations: usize,
+}
+
+/// A geodesic leaving a point at an azimuth or going through two points.
+#[derive(Debug, Clone, Copy)]
+pub enum Course {
+    Heading { x: LatLng, az: Az },
+    Through { x: LatLng, y: LatLng },
+}
+
+#[derive(Debug)]
+pub struct IntersectionProblem {
+    pub a: Course,
+    pub b: Course,
+}
+
+/// Where two courses cross, with the distances along each from its start,
+/// negative when behind it.
+#[derive(Debug, Clone, Copy)]
+pub struct Crossing {
+    pub y: LatLng,
+    pub sa: Dist,
+    pub sb: Dist,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Intersection {
+    /// The courses cross twice, first nearest to their starts and again about
+    /// half way around the earth.
+    Crossings([Crossing; 2]),
+    /// The courses cross nearest to their starts but the search for the
+    /// crossing half way around the earth found them parallel or coincident
+    /// there.
+    Crossing(Crossing),
+    /// The courses are so nearly parallel, without lying along one another,
+    /// that where they cross can't be found.
+    Parallel,
+    /// The courses lie along the same geodesic.
+    Coincident,
 }
 
 impl fmt::Display for Az {
@@ -82,3 +167,19 @@ impl fmt::Display for InverseSolution {
         }
     }
 }
+
+impl fmt::Display for TrackProblem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(x={}, y={}, p={})", self.x, self.y, self.p)
+    }
+}
+
+impl fmt::Display for TrackSolution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "(xt={}, at={}, closest={})",
+            self.cross_track, self.along_track, self.closest
+        )
+    }
+}
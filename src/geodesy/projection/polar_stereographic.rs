@@ -0,0 +1,287 @@
+// The polar stereographic projection of the ellipsoid, a conformal projection
+// from a pole, found from the conformal latitude in closed form. With a scale
+// of 0.994 at the pole and a false origin of 2000km it is the Universal Polar
+// Stereographic (UPS) projection, the complement of UTM near the poles.
+//
+// SEE: <https://geographiclib.sourceforge.io/C++/doc/classGeographicLib_1_1PolarStereographic.html>
+use crate::{
+    earth::ellipsoid::{flattening, is_valid, Ellipsoid},
+    units::{convert::plus_minus_pi_rad, Meter, Rad, Radius},
+};
+use std::{f64::consts::FRAC_PI_2, fmt};
+
+use super::{
+    super::{
+        error::GeodesyError, latitude::AuxiliaryLatitudes, latlng::LatLng, vincenty::check_lat,
+    },
+    transverse_mercator::{Projected, Unprojected},
+};
+
+/// The hemisphere of a pole or of a projected position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hemisphere {
+    North,
+    South,
+}
+
+impl fmt::Display for Hemisphere {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Hemisphere::North => write!(f, "N"),
+            Hemisphere::South => write!(f, "S"),
+        }
+    }
+}
+
+/// The polar stereographic projection of an ellipsoid from a pole, with the
+/// scale at the pole and the false origin. The grid north is along the 180°
+/// meridian from the north pole and along the 0° meridian from the south pole.
+///
+/// ```
+/// # use auxillary_sphere::{
+/// #     earth::ellipsoid::WGS84,
+/// #     geodesy::{
+/// #         latlng::LatLng,
+/// #         projection::polar_stereographic::{Hemisphere, PolarStereographic},
+/// #     },
+/// #     units::Meter,
+/// # };
+/// let ups = PolarStereographic::new(&WGS84, 0.994, (Meter(2000000.0), Meter(2000000.0))).unwrap();
+///
+/// let pole = ups.forward(Hemisphere::North, &LatLng::new(90.0, 0.0)).unwrap();
+/// assert_eq!((pole.easting.0, pole.northing.0), (2000000.0, 2000000.0));
+/// assert_eq!(pole.scale, 0.994);
+///
+/// let x = LatLng::new(-85.0, 45.0);
+/// let grid = ups.forward(Hemisphere::South, &x).unwrap();
+/// let back = ups.reverse(Hemisphere::South, grid.easting, grid.northing);
+/// assert!((back.x.lat.0 - x.lat.0).abs() < 1e-14);
+/// assert!((back.x.lng.0 - x.lng.0).abs() < 1e-14);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct PolarStereographic {
+    lats: AuxiliaryLatitudes,
+    a: f64,
+    e2: f64,
+    // The distance from the pole per unit of 1 / (sec χ + tan χ) of the
+    // conformal latitude, χ, 2 k₀ a / c with c = (1 - ƒ) exp(e tanh⁻¹ e).
+    scale_r: f64,
+    k0: f64,
+    false_easting: f64,
+    false_northing: f64,
+}
+
+impl PolarStereographic {
+    /// The projection with the scale, k₀, at the pole and the false easting
+    /// and northing added to the projected coordinates.
+    pub fn new(
+        ellipsoid: &Ellipsoid,
+        k0: f64,
+        (false_easting, false_northing): (Meter, Meter),
+    ) -> Result<Self, GeodesyError> {
+        if !is_valid(ellipsoid) {
+            return Err(GeodesyError::InvalidEllipsoid);
+        }
+        if !(k0 > 0.0 && k0.is_finite()) {
+            return Err(GeodesyError::InvalidProjection);
+        }
+
+        let lats = AuxiliaryLatitudes::new(ellipsoid)?;
+        let Radius(Meter(a)) = ellipsoid.equatorial_r;
+        let c = (1.0 - flattening(ellipsoid)) * lats.eatanhe(1.0).exp();
+
+        Ok(PolarStereographic {
+            lats,
+            a,
+            e2: ellipsoid.eccentricity_sq(),
+            scale_r: 2.0 * k0 * a / c,
+            k0,
+            false_easting: false_easting.0,
+            false_northing: false_northing.0,
+        })
+    }
+
+    // The point scale from the distance from the pole and the tangent of the
+    // geodetic latitude, ρ / (N cos φ).
+    fn point_scale(&self, rho: f64, tau: f64) -> f64 {
+        let sec = 1.0_f64.hypot(tau);
+        rho / self.a * sec * (1.0 - self.e2 + self.e2 / (sec * sec)).sqrt()
+    }
+
+    /// Projects a position from the pole of a hemisphere, with the latitude
+    /// rejected outside the range -90° .. 90°. Positions in the other
+    /// hemisphere are projected too, with a scale growing without bound
+    /// towards the other pole.
+    pub fn forward(&self, pole: Hemisphere, x: &LatLng) -> Result<Projected, GeodesyError> {
+        let Rad(lat) = check_lat(x.lat)?;
+        let Rad(lng) = plus_minus_pi_rad(x.lng);
+
+        // Latitudes are taken from the equator towards the pole.
+        let lat = match pole {
+            Hemisphere::North => lat,
+            Hemisphere::South => -lat,
+        };
+        let at_pole = lat == FRAC_PI_2;
+
+        let tau = lat.tan();
+        let taup = self.lats.taupf(tau);
+        let t = 1.0_f64.hypot(taup) + taup.abs();
+        let rho = self.scale_r
+            * if taup >= 0.0 {
+                if at_pole {
+                    0.0
+                } else {
+                    1.0 / t
+                }
+            } else {
+                t
+            };
+
+        let (sin_lng, cos_lng) = lng.sin_cos();
+        let (y, convergence) = match pole {
+            Hemisphere::North => (-rho * cos_lng, lng),
+            Hemisphere::South => (rho * cos_lng, -lng),
+        };
+
+        Ok(Projected {
+            easting: Meter(self.false_easting + rho * sin_lng),
+            northing: Meter(self.false_northing + y),
+            convergence: Rad(convergence),
+            scale: if at_pole {
+                self.k0
+            } else {
+                self.point_scale(rho, tau)
+            },
+        })
+    }
+
+    /// The position of projected coordinates from the pole of a hemisphere.
+    pub fn reverse(
+        &self,
+        pole: Hemisphere,
+        Meter(easting): Meter,
+        Meter(northing): Meter,
+    ) -> Unprojected {
+        let x = easting - self.false_easting;
+        let y = northing - self.false_northing;
+        let rho = x.hypot(y);
+
+        let t = if rho != 0.0 {
+            rho / self.scale_r
+        } else {
+            f64::EPSILON * f64::EPSILON
+        };
+        let taup = (1.0 / t - t) / 2.0;
+        let tau = self.lats.tauf(taup);
+        let lat = tau.atan();
+
+        let (lat, lng, convergence) = match pole {
+            Hemisphere::North => {
+                let lng = x.atan2(-y);
+                (lat, lng, lng)
+            }
+            Hemisphere::South => {
+                let lng = x.atan2(y);
+                (-lat, lng, -lng)
+            }
+        };
+
+        Unprojected {
+            x: LatLng {
+                lat: Rad(lat),
+                lng: Rad(lng),
+            },
+            convergence: Rad(convergence),
+            scale: if rho != 0.0 {
+                self.point_scale(rho, tau)
+            } else {
+                self.k0
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::earth::ellipsoid::{NAD83, WGS84};
+
+    fn ups() -> PolarStereographic {
+        PolarStereographic::new(&WGS84, 0.994, (Meter(2000000.0), Meter(2000000.0))).unwrap()
+    }
+
+    #[test]
+    #[allow(clippy::excessive_precision)]
+    fn published() {
+        // The UPS test points of PROJ from the north pole on the GRS80
+        // ellipsoid, the ellipsoid of NAD83, as (λ, φ, E, N).
+        // SEE: <https://github.com/OSGeo/PROJ/blob/master/test/gie/builtins.gie>
+        let ups =
+            PolarStereographic::new(&NAD83, 0.994, (Meter(2000000.0), Meter(2000000.0))).unwrap();
+        for (lng, lat, easting, northing) in [
+            (2.0, 1.0, 2433455.5634384668, -10412543.301512826),
+            (2.0, -1.0, 2448749.1185681992, -10850493.419804076),
+            (-2.0, 1.0, 1566544.4365615332, -10412543.301512826),
+            (-2.0, -1.0, 1551250.8814318008, -10850493.419804076),
+        ] {
+            let grid = ups
+                .forward(Hemisphere::North, &LatLng::new(lat, lng))
+                .unwrap();
+            assert!((grid.easting.0 - easting).abs() < 1e-6);
+            assert!((grid.northing.0 - northing).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn near_the_pole() {
+        // The scale tends to k₀ towards the pole.
+        let ups = ups();
+        for pole in [Hemisphere::North, Hemisphere::South] {
+            let lat = match pole {
+                Hemisphere::North => 89.99999,
+                Hemisphere::South => -89.99999,
+            };
+            let grid = ups.forward(pole, &LatLng::new(lat, 30.0)).unwrap();
+            assert!((grid.scale - 0.994).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn grid_north() {
+        // Grid north from the north pole is along 180° and from the south
+        // pole along 0°, with 90° east along grid east from both.
+        let ups = ups();
+        let north = ups
+            .forward(Hemisphere::North, &LatLng::new(85.0, 180.0))
+            .unwrap();
+        let south = ups
+            .forward(Hemisphere::South, &LatLng::new(-85.0, 0.0))
+            .unwrap();
+        let east = ups
+            .forward(Hemisphere::North, &LatLng::new(85.0, 90.0))
+            .unwrap();
+
+        assert!((north.easting.0 - 2000000.0).abs() < 1e-6 && north.northing.0 > 2000000.0);
+        assert!((south.easting.0 - 2000000.0).abs() < 1e-6 && south.northing.0 > 2000000.0);
+        assert!((east.northing.0 - 2000000.0).abs() < 1e-6 && east.easting.0 > 2000000.0);
+    }
+
+    #[test]
+    fn round_trip() {
+        let ups = ups();
+        for lat in [60.0, 80.0, 84.0, 88.0, 89.9] {
+            for lng in [-180.0, -135.0, -10.0, 0.0, 45.0, 170.0] {
+                for (pole, lat) in [(Hemisphere::North, lat), (Hemisphere::South, -lat)] {
+                    let x = LatLng::new(lat, lng);
+                    let grid = ups.forward(pole, &x).unwrap();
+                    let back = ups.reverse(pole, grid.easting, grid.northing);
+
+                    assert!((back.x.lat.0 - x.lat.0).abs() < 1e-14, "{lat}, {lng}");
+                    // Either of ±180° for the meridian opposite grid north.
+                    assert!((back.x.lng.0 - x.lng.0).sin().abs() < 1e-13, "{lat}, {lng}");
+                    assert!((back.scale - grid.scale).abs() < 1e-14);
+                }
+            }
+        }
+    }
+}
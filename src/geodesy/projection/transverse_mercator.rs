@@ -0,0 +1,621 @@
+// The Transverse Mercator projection of the ellipsoid with Krüger's series in
+// the third flattening, n, to n⁶, accurate to a few nanometres within 3900km
+// of the central meridian.
+//
+// The geodetic latitude is first taken to the conformal latitude, mapping the
+// ellipsoid conformally onto a sphere, and then the sphere is projected with
+// the spherical Transverse Mercator, to ξ' + iη'. Krüger's series takes these
+// to the projected ξ + iη, in units of the rectifying radius, A, so that the
+// central meridian is mapped true to scale.
+//
+// Positions more than 90° of longitude from the central meridian are folded
+// back across the meridian 90° away, as in GeographicLib, with their northing
+// taken from beyond the pole.
+//
+// SEE: <https://arxiv.org/abs/1002.1417>
+use crate::{
+    earth::ellipsoid::{is_valid, Ellipsoid},
+    units::{convert::plus_minus_pi_rad, Meter, Rad, Radius},
+};
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use super::super::{
+    error::GeodesyError,
+    latitude::{AuxiliaryLatitudes, Latitude},
+    latlng::LatLng,
+    vincenty::check_lat,
+};
+
+const ORDER: usize = 6;
+
+/// A position projected onto the plane, with the meridian convergence, the
+/// angle of grid north clockwise from true north, and the point scale.
+#[derive(Debug, Clone, Copy)]
+pub struct Projected {
+    pub easting: Meter,
+    pub northing: Meter,
+    pub convergence: Rad,
+    pub scale: f64,
+}
+
+/// A position from the plane, with the meridian convergence and point scale
+/// there.
+#[derive(Debug, Clone, Copy)]
+pub struct Unprojected {
+    pub x: LatLng,
+    pub convergence: Rad,
+    pub scale: f64,
+}
+
+/// The Transverse Mercator projection of an ellipsoid about a central
+/// meridian, with the scale on that meridian and the false origin.
+///
+/// ```
+/// # use auxillary_sphere::{
+/// #     earth::ellipsoid::WGS84,
+/// #     geodesy::{latlng::LatLng, projection::transverse_mercator::TransverseMercator},
+/// #     units::Meter,
+/// # };
+/// // UTM zone 31, north.
+/// let tm = TransverseMercator::new(
+///     &WGS84,
+///     &LatLng::new(0.0, 3.0),
+///     0.9996,
+///     (Meter(500000.0), Meter(0.0)),
+/// )
+/// .unwrap();
+///
+/// let eiffel = LatLng::new(48.85826, 2.29451);
+/// let grid = tm.forward(&eiffel).unwrap();
+/// assert_eq!(grid.easting.0.floor(), 448252.0);
+/// assert_eq!(grid.northing.0.floor(), 5411939.0);
+///
+/// let back = tm.reverse(grid.easting, grid.northing);
+/// assert!((back.x.lat.0 - eiffel.lat.0).abs() < 1e-14);
+/// assert!((back.x.lng.0 - eiffel.lng.0).abs() < 1e-14);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct TransverseMercator {
+    lats: AuxiliaryLatitudes,
+    a: f64,
+    e2: f64,
+    // The rectifying radius scaled by the scale on the central meridian, k₀ A.
+    k0_a: f64,
+    k0: f64,
+    lng0: f64,
+    false_easting: f64,
+    // The false northing less the northing of the true origin.
+    false_northing: f64,
+    // Krüger's coefficients, α for the forward series and β for its reversion,
+    // at alp[j] and bet[j] for j = 1 .. 6.
+    alp: [f64; ORDER + 1],
+    bet: [f64; ORDER + 1],
+}
+
+// Evaluates a polynomial in n from its coefficients in increasing powers.
+fn poly(n: f64, c: &[f64]) -> f64 {
+    c.iter().rev().fold(0.0, |acc, ck| acc * n + ck)
+}
+
+impl TransverseMercator {
+    /// The projection with the true origin, on the central meridian, the
+    /// scale there, k₀, and the false easting and northing given to the true
+    /// origin, with its latitude rejected outside the range -90° .. 90°.
+    pub fn new(
+        ellipsoid: &Ellipsoid,
+        origin: &LatLng,
+        k0: f64,
+        (false_easting, false_northing): (Meter, Meter),
+    ) -> Result<Self, GeodesyError> {
+        if !is_valid(ellipsoid) {
+            return Err(GeodesyError::InvalidEllipsoid);
+        }
+        if !(k0 > 0.0 && k0.is_finite()) {
+            return Err(GeodesyError::InvalidProjection);
+        }
+        let Rad(lat0) = check_lat(origin.lat)?;
+
+        let Radius(Meter(a)) = ellipsoid.equatorial_r;
+        let n = ellipsoid.third_flattening();
+        let n2 = n * n;
+        let rectifying_r =
+            a / (1.0 + n) * (1.0 + n2 * (1.0 / 4.0 + n2 * (1.0 / 64.0 + n2 / 256.0)));
+
+        let alp = [
+            0.0,
+            poly(
+                n,
+                &[
+                    0.0,
+                    1.0 / 2.0,
+                    -2.0 / 3.0,
+                    5.0 / 16.0,
+                    41.0 / 180.0,
+                    -127.0 / 288.0,
+                    7891.0 / 37800.0,
+                ],
+            ),
+            poly(
+                n,
+                &[
+                    0.0,
+                    0.0,
+                    13.0 / 48.0,
+                    -3.0 / 5.0,
+                    557.0 / 1440.0,
+                    281.0 / 630.0,
+                    -1983433.0 / 1935360.0,
+                ],
+            ),
+            poly(
+                n,
+                &[
+                    0.0,
+                    0.0,
+                    0.0,
+                    61.0 / 240.0,
+                    -103.0 / 140.0,
+                    15061.0 / 26880.0,
+                    167603.0 / 181440.0,
+                ],
+            ),
+            poly(
+                n,
+                &[
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    49561.0 / 161280.0,
+                    -179.0 / 168.0,
+                    6601661.0 / 7257600.0,
+                ],
+            ),
+            poly(
+                n,
+                &[
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    34729.0 / 80640.0,
+                    -3418889.0 / 1995840.0,
+                ],
+            ),
+            poly(
+                n,
+                &[0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 212378941.0 / 319334400.0],
+            ),
+        ];
+        let bet = [
+            0.0,
+            poly(
+                n,
+                &[
+                    0.0,
+                    1.0 / 2.0,
+                    -2.0 / 3.0,
+                    37.0 / 96.0,
+                    -1.0 / 360.0,
+                    -81.0 / 512.0,
+                    96199.0 / 604800.0,
+                ],
+            ),
+            poly(
+                n,
+                &[
+                    0.0,
+                    0.0,
+                    1.0 / 48.0,
+                    1.0 / 15.0,
+                    -437.0 / 1440.0,
+                    46.0 / 105.0,
+                    -1118711.0 / 3870720.0,
+                ],
+            ),
+            poly(
+                n,
+                &[
+                    0.0,
+                    0.0,
+                    0.0,
+                    17.0 / 480.0,
+                    -37.0 / 840.0,
+                    -209.0 / 4480.0,
+                    5569.0 / 90720.0,
+                ],
+            ),
+            poly(
+                n,
+                &[
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    4397.0 / 161280.0,
+                    -11.0 / 504.0,
+                    -830251.0 / 7257600.0,
+                ],
+            ),
+            poly(
+                n,
+                &[
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    0.0,
+                    4583.0 / 161280.0,
+                    -108847.0 / 3991680.0,
+                ],
+            ),
+            poly(n, &[0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 20648693.0 / 638668800.0]),
+        ];
+
+        // On the central meridian the northing is in proportion to the
+        // rectifying latitude.
+        let lats = AuxiliaryLatitudes::new(ellipsoid)?;
+        let mu0 = lats.geodetic_to(lat0, Latitude::Rectifying);
+
+        Ok(TransverseMercator {
+            lats,
+            a,
+            e2: ellipsoid.eccentricity_sq(),
+            k0_a: k0 * rectifying_r,
+            k0,
+            lng0: origin.lng.0,
+            false_easting: false_easting.0,
+            false_northing: false_northing.0 - k0 * rectifying_r * mu0,
+            alp,
+            bet,
+        })
+    }
+
+    // The convergence, γ', and scale, k', of the spherical Transverse Mercator
+    // of the conformal sphere, from tan φ, the tangent of the conformal
+    // latitude and the longitude from the central meridian.
+    fn sphere_scale(&self, sin_lat: f64, tau: f64, taup: f64, lng: f64) -> (f64, f64) {
+        let (sin_lng, cos_lng) = lng.sin_cos();
+        let gamma = (taup * sin_lng).atan2(1.0_f64.hypot(taup) * cos_lng);
+        let k =
+            (1.0 - self.e2 * sin_lat * sin_lat).sqrt() * 1.0_f64.hypot(tau) / taup.hypot(cos_lng);
+        (gamma, k)
+    }
+
+    /// Projects a position, with the latitude rejected outside the range
+    /// -90° .. 90°. The projection is accurate to a few nanometres within
+    /// 3900km of the central meridian, about 35° of longitude on the equator,
+    /// and less so further out. The points on the equator 90° of longitude
+    /// from the central meridian, where the projection is infinite, are
+    /// rejected as [GeodesyError::InvalidProjection].
+    pub fn forward(&self, x: &LatLng) -> Result<Projected, GeodesyError> {
+        let Rad(lat) = check_lat(x.lat)?;
+        let Rad(lng) = plus_minus_pi_rad(Rad(x.lng.0 - self.lng0));
+        let backside = lng.abs() > FRAC_PI_2;
+        let lng = if backside {
+            (PI - lng.abs()).copysign(lng)
+        } else {
+            lng
+        };
+
+        let (sin_lat, cos_lat) = lat.sin_cos();
+        let tau = sin_lat / cos_lat;
+        let taup = self.lats.taupf(tau);
+        let (sin_lng, cos_lng) = lng.sin_cos();
+        let denom = taup.hypot(cos_lng);
+        if denom < f64::EPSILON {
+            return Err(GeodesyError::InvalidProjection);
+        }
+
+        // The spherical Transverse Mercator of the conformal sphere.
+        let xip = taup.atan2(cos_lng);
+        let etap = (sin_lng / denom).asinh();
+        let (gammap, kp) = self.sphere_scale(sin_lat, tau, taup, lng);
+
+        // Krüger's series, ζ = ζ' + Σ αⱼ sin 2jζ' with ζ = ξ + iη, and its
+        // derivative, dζ/dζ' = p - iq.
+        let (mut xi, mut eta, mut p, mut q) = (xip, etap, 1.0, 0.0);
+        for (j, alp) in self.alp.iter().enumerate().skip(1) {
+            let j2 = 2.0 * j as f64;
+            let (s, c) = (j2 * xip).sin_cos();
+            let (sh, ch) = ((j2 * etap).sinh(), (j2 * etap).cosh());
+            xi += alp * s * ch;
+            eta += alp * c * sh;
+            p += j2 * alp * c * ch;
+            q += j2 * alp * s * sh;
+        }
+        let gamma = gammap + q.atan2(p);
+
+        // Unfolded from the near side, with the equator taken as in the
+        // southern hemisphere.
+        let (xi, gamma) = if backside {
+            let half_turn = if lat > 0.0 { PI } else { -PI };
+            (half_turn - xi, half_turn * lng.signum() - gamma)
+        } else {
+            (xi, gamma)
+        };
+
+        Ok(Projected {
+            easting: Meter(self.false_easting + self.k0_a * eta),
+            northing: Meter(self.false_northing + self.k0_a * xi),
+            convergence: Rad(gamma),
+            scale: self.k0_a / self.a * kp * p.hypot(q),
+        })
+    }
+
+    /// The position of projected coordinates, with its longitude normalized
+    /// to -180° .. 180°.
+    pub fn reverse(&self, Meter(easting): Meter, Meter(northing): Meter) -> Unprojected {
+        let xi = (northing - self.false_northing) / self.k0_a;
+        let eta = (easting - self.false_easting) / self.k0_a;
+        // Folded back to the near side from beyond the poles.
+        let xi_sign = 1.0_f64.copysign(xi);
+        let backside = xi.abs() > FRAC_PI_2;
+        let xi = if backside { PI * xi_sign - xi } else { xi };
+
+        // The reverted series, ζ' = ζ - Σ βⱼ sin 2jζ, and its derivative,
+        // dζ'/dζ = p + iq.
+        let (mut xip, mut etap, mut p, mut q) = (xi, eta, 1.0, 0.0);
+        for (j, bet) in self.bet.iter().enumerate().skip(1) {
+            let j2 = 2.0 * j as f64;
+            let (s, c) = (j2 * xi).sin_cos();
+            let (sh, ch) = ((j2 * eta).sinh(), (j2 * eta).cosh());
+            xip -= bet * s * ch;
+            etap -= bet * c * sh;
+            p -= j2 * bet * c * ch;
+            q += j2 * bet * s * sh;
+        }
+
+        // The reverse of the spherical Transverse Mercator of the conformal
+        // sphere.
+        let (sin_xip, cos_xip) = xip.sin_cos();
+        let sinh_etap = etap.sinh();
+        let taup = sin_xip / sinh_etap.hypot(cos_xip);
+        let lng = sinh_etap.atan2(cos_xip);
+        let tau = self.lats.tauf(taup);
+        let lat = tau.atan();
+
+        let (gammap, kp) = self.sphere_scale(lat.sin(), tau, taup, lng);
+        let gamma = gammap + q.atan2(p);
+
+        let (lng, gamma) = if backside {
+            let half_turn = PI.copysign(eta);
+            (half_turn - lng, half_turn * xi_sign - gamma)
+        } else {
+            (lng, gamma)
+        };
+
+        Unprojected {
+            x: LatLng {
+                lat: Rad(lat),
+                lng: plus_minus_pi_rad(Rad(lng + self.lng0)),
+            },
+            convergence: Rad(gamma),
+            scale: self.k0_a / self.a * kp / p.hypot(q),
+        }
+    }
+
+    /// The scale, k₀, on the central meridian.
+    pub fn central_scale(&self) -> f64 {
+        self.k0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        earth::ellipsoid::{AIRY, NAD83, WGS84},
+        geodesy::latitude::Latitude,
+        units::{convert::deg_to_rad, Deg, DMS},
+    };
+
+    #[test]
+    fn central_meridian() {
+        // Along the central meridian the northing is the meridian arc scaled
+        // by k₀, from the rectifying latitude, with no convergence.
+        let tm = TransverseMercator::new(
+            &WGS84,
+            &LatLng::new(0.0, 0.0),
+            0.9996,
+            (Meter(0.0), Meter(0.0)),
+        )
+        .unwrap();
+        let lats = AuxiliaryLatitudes::new(&WGS84).unwrap();
+        let Meter(quarter) = WGS84.quarter_meridian();
+
+        for lat in [1.0, 30.0, 45.0, 60.0, 89.0] {
+            let phi = deg_to_rad(Deg(lat));
+            let grid = tm
+                .forward(&LatLng {
+                    lat: phi,
+                    lng: Rad(0.0),
+                })
+                .unwrap();
+            let Rad(mu) = lats
+                .convert(phi, Latitude::Geodetic, Latitude::Rectifying)
+                .unwrap();
+
+            assert!(grid.easting.0.abs() < 1e-9);
+            assert!((grid.northing.0 - 0.9996 * quarter * mu / FRAC_PI_2).abs() < 1e-6);
+            assert!(grid.convergence.0.abs() < 1e-15);
+            assert!((grid.scale - 0.9996).abs() < 1e-15);
+        }
+    }
+
+    #[test]
+    #[allow(clippy::excessive_precision)]
+    fn published() {
+        // The Transverse Mercator test points of PROJ on the GRS80 ellipsoid,
+        // the ellipsoid of NAD83, as (λ, φ, E, N).
+        // SEE: <https://github.com/OSGeo/PROJ/blob/master/test/gie/builtins.gie>
+        let tm = TransverseMercator::new(
+            &NAD83,
+            &LatLng::new(0.0, 0.0),
+            1.0,
+            (Meter(0.0), Meter(0.0)),
+        )
+        .unwrap();
+        for (lng, lat, easting, northing) in [
+            (2.0, 1.0, 222650.79679758527, 110642.22941193319),
+            (2.0, -1.0, 222650.79679758527, -110642.22941193319),
+            (-2.0, 1.0, -222650.79679758527, 110642.22941193319),
+            (-2.0, -1.0, -222650.79679758527, -110642.22941193319),
+        ] {
+            let grid = tm.forward(&LatLng::new(lat, lng)).unwrap();
+            assert!((grid.easting.0 - easting).abs() < 1e-6);
+            assert!((grid.northing.0 - northing).abs() < 1e-6);
+        }
+
+        // The Ordnance Survey's worked example, Caister Water Tower on the
+        // National Grid, with a true origin at 49°N 2°W given the false
+        // coordinates (400km, -100km).
+        // SEE: <https://www.ordnancesurvey.co.uk/documents/resources/guide-coordinate-systems-great-britain.pdf>
+        let grid = TransverseMercator::new(
+            &AIRY,
+            &LatLng::new(49.0, -2.0),
+            0.9996012717,
+            (Meter(400000.0), Meter(-100000.0)),
+        )
+        .unwrap();
+        let origin = grid.forward(&LatLng::new(49.0, -2.0)).unwrap();
+        let caister = grid
+            .forward(&LatLng::from((
+                DMS {
+                    deg: 52,
+                    min: 39,
+                    sec: 27.2531,
+                },
+                DMS {
+                    deg: 1,
+                    min: 43,
+                    sec: 4.5177,
+                },
+            )))
+            .unwrap();
+
+        assert!((origin.easting.0 - 400000.0).abs() < 1e-9);
+        assert!((origin.northing.0 + 100000.0).abs() < 1e-9);
+        assert!((caister.easting.0 - 651409.903).abs() < 0.001);
+        assert!((caister.northing.0 - 313177.270).abs() < 0.001);
+    }
+
+    #[test]
+    fn far_side() {
+        // Beyond 90° from the central meridian the far side is folded back,
+        // continuous across that meridian and over the poles, with only the
+        // points on the equator where the projection is infinite rejected.
+        let tm = TransverseMercator::new(
+            &WGS84,
+            &LatLng::new(0.0, 0.0),
+            0.9996,
+            (Meter(0.0), Meter(0.0)),
+        )
+        .unwrap();
+        for lng in [90.0, -90.0] {
+            assert_eq!(
+                tm.forward(&LatLng::new(0.0, lng)).unwrap_err(),
+                GeodesyError::InvalidProjection
+            );
+        }
+
+        for (near, far) in [
+            ((89.99, 89.999999999), (89.99, 90.000000001)),
+            ((-10.0, -89.999999999), (-10.0, -90.000000001)),
+            ((89.999999, 135.0), (89.999999, -45.0)),
+            ((-89.999999, 179.0), (-89.999999, -1.0)),
+        ] {
+            let near = tm.forward(&LatLng::new(near.0, near.1)).unwrap();
+            let far = tm.forward(&LatLng::new(far.0, far.1)).unwrap();
+            assert!((near.easting.0 - far.easting.0).abs() < 1.0);
+            assert!((near.northing.0 - far.northing.0).abs() < 1.0);
+        }
+
+        for (lat, lng) in [
+            (90.0, 135.0),
+            (89.9999, 179.0),
+            (89.99, 90.5),
+            (60.0, 150.0),
+            (-45.0, -100.0),
+            (0.0, 180.0),
+            (-30.0, -150.0),
+        ] {
+            // Near a pole, where the longitude is lost, the differences in
+            // longitude and convergence are taken along the parallel.
+            let x = LatLng::new(lat, lng);
+            let grid = tm.forward(&x).unwrap();
+            let back = tm.reverse(grid.easting, grid.northing);
+            let cos_lat = x.lat.0.cos();
+
+            assert!((back.x.lat.0 - x.lat.0).abs() < 1e-13, "{lat}, {lng}");
+            assert!(
+                (back.x.lng.0 - x.lng.0).abs() * cos_lat < 1e-13,
+                "{lat}, {lng}"
+            );
+            assert!((back.convergence.0 - grid.convergence.0).abs() * cos_lat < 1e-13);
+            assert!((back.scale - grid.scale).abs() < 1e-13);
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let tm = TransverseMercator::new(
+            &WGS84,
+            &LatLng::new(0.0, -75.0),
+            0.9996,
+            (Meter(500000.0), Meter(0.0)),
+        )
+        .unwrap();
+        for lat in (-80..=84).step_by(4).map(|d| d as f64) {
+            for lng in [-105.0, -90.0, -78.5, -75.0, -72.0, -60.0] {
+                let x = LatLng::new(lat, lng);
+                let grid = tm.forward(&x).unwrap();
+                let back = tm.reverse(grid.easting, grid.northing);
+
+                assert!((back.x.lat.0 - x.lat.0).abs() < 1e-13, "{lat}, {lng}");
+                assert!((back.x.lng.0 - x.lng.0).abs() < 1e-13, "{lat}, {lng}");
+                assert!((back.convergence.0 - grid.convergence.0).abs() < 1e-13);
+                assert!((back.scale - grid.scale).abs() < 1e-13);
+            }
+        }
+    }
+
+    #[test]
+    fn convergence_and_scale() {
+        // Found from small steps along the meridian and the parallel.
+        let tm = TransverseMercator::new(
+            &WGS84,
+            &LatLng::new(0.0, 0.0),
+            0.9996,
+            (Meter(0.0), Meter(0.0)),
+        )
+        .unwrap();
+        let x = LatLng::new(52.0, 2.5);
+        let grid = tm.forward(&x).unwrap();
+
+        let d = deg_to_rad(Deg(1e-5)).0;
+        let north = tm
+            .forward(&LatLng {
+                lat: Rad(x.lat.0 + d),
+                ..x
+            })
+            .unwrap();
+        let east = tm
+            .forward(&LatLng {
+                lng: Rad(x.lng.0 + d),
+                ..x
+            })
+            .unwrap();
+
+        // A meridian east of the central meridian heads west of grid north.
+        let de = north.easting.0 - grid.easting.0;
+        let dn = north.northing.0 - grid.northing.0;
+        assert!(grid.convergence.0 > 0.0);
+        assert!((-de.atan2(dn) - grid.convergence.0).abs() < 1e-7);
+
+        let Radius(Meter(n)) = WGS84.prime_vertical_r(x.lat);
+        let along = (east.easting.0 - grid.easting.0).hypot(east.northing.0 - grid.northing.0);
+        assert!((along / (n * x.lat.0.cos() * d) - grid.scale).abs() < 1e-7);
+    }
+}
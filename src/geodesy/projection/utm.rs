@@ -0,0 +1,323 @@
+// The Universal Transverse Mercator (UTM) and Universal Polar Stereographic
+// (UPS) grids. UTM covers -80° .. 84° with 60 zones, each 6° wide about its
+// central meridian, widened or narrowed off the south west of Norway and
+// around Svalbard. UPS covers the polar caps beyond.
+//
+// SEE: <https://en.wikipedia.org/wiki/Universal_Transverse_Mercator_coordinate_system>
+use crate::{
+    earth::ellipsoid::Ellipsoid,
+    units::{
+        convert::{plus_minus_pi_rad, rad_to_deg},
+        Deg, Meter, Rad,
+    },
+};
+use std::fmt;
+
+use super::{
+    super::{error::GeodesyError, latlng::LatLng, vincenty::check_lat},
+    polar_stereographic::{Hemisphere, PolarStereographic},
+    transverse_mercator::{TransverseMercator, Unprojected},
+};
+
+const UTM_K0: f64 = 0.9996;
+const UTM_FALSE_EASTING: f64 = 500000.0;
+const UTM_FALSE_NORTHING_SOUTH: f64 = 10000000.0;
+const UPS_K0: f64 = 0.994;
+const UPS_FALSE_ORIGIN: f64 = 2000000.0;
+
+// The latitude bands of UTM, each 8° from -80° with X stretched to 84°.
+const BANDS: &[u8] = b"CDEFGHJKLMNPQRSTUVWX";
+
+/// A UTM zone, 1 .. 60, or the polar UPS grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Zone {
+    Utm(u8),
+    Ups,
+}
+
+impl fmt::Display for Zone {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Zone::Utm(zone) => write!(f, "{}", zone),
+            Zone::Ups => write!(f, "UPS"),
+        }
+    }
+}
+
+/// A position on the UTM or UPS grid, with the meridian convergence and point
+/// scale there.
+#[derive(Debug, Clone, Copy)]
+pub struct Utm {
+    pub zone: Zone,
+    /// The latitude band, C .. X for UTM, A or B south and Y or Z north, west
+    /// and east of the 0° meridian, for UPS.
+    pub band: char,
+    pub hemisphere: Hemisphere,
+    pub easting: Meter,
+    pub northing: Meter,
+    pub convergence: Rad,
+    pub scale: f64,
+}
+
+impl fmt::Display for Utm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let zone = match self.zone {
+            Zone::Utm(zone) => zone.to_string(),
+            Zone::Ups => String::new(),
+        };
+        write!(
+            f,
+            "{}{} {:.0} {:.0}",
+            zone, self.band, self.easting.0, self.northing.0
+        )
+    }
+}
+
+/// The standard zone of a position, the UTM zone between -80° and 84°, with
+/// the exceptions of Norway and Svalbard, and UPS beyond.
+pub fn zone(x: &LatLng) -> Result<Zone, GeodesyError> {
+    let Deg(lat) = rad_to_deg(check_lat(x.lat)?);
+    let Deg(lng) = rad_to_deg(plus_minus_pi_rad(x.lng));
+    Ok(standard_zone(lat, lng))
+}
+
+// The standard zone from the latitude in range and the normalized longitude,
+// in degrees.
+fn standard_zone(lat: f64, lng: f64) -> Zone {
+    if !(-80.0..84.0).contains(&lat) {
+        return Zone::Ups;
+    }
+
+    let lng = match lng.floor() as i32 {
+        180 => -180,
+        lng => lng,
+    };
+    let zone = (lng + 186) / 6;
+
+    let zone = match band_index(lat) {
+        // The zone 32V is widened west to 3° over Norway.
+        17 if zone == 31 && lng >= 3 => 32,
+        // Around Svalbard the even zones of band X are dropped, the odd zones
+        // 31 .. 37 widened to cover them.
+        19 if (0..42).contains(&lng) => 2 * ((lng + 183) / 12) + 1,
+        _ => zone,
+    };
+    Zone::Utm(zone as u8)
+}
+
+fn band_index(lat: f64) -> usize {
+    (((lat + 80.0) / 8.0).floor() as usize).min(BANDS.len() - 1)
+}
+
+fn band(lat: f64, lng: f64) -> char {
+    if lat >= 84.0 {
+        if lng < 0.0 {
+            'Y'
+        } else {
+            'Z'
+        }
+    } else if lat < -80.0 {
+        if lng < 0.0 {
+            'A'
+        } else {
+            'B'
+        }
+    } else {
+        BANDS[band_index(lat)] as char
+    }
+}
+
+fn utm_projection(
+    ellipsoid: &Ellipsoid,
+    zone: u8,
+    hemisphere: Hemisphere,
+) -> Result<TransverseMercator, GeodesyError> {
+    if !(1..=60).contains(&zone) {
+        return Err(GeodesyError::InvalidProjection);
+    }
+
+    let false_northing = match hemisphere {
+        Hemisphere::North => 0.0,
+        Hemisphere::South => UTM_FALSE_NORTHING_SOUTH,
+    };
+    TransverseMercator::new(
+        ellipsoid,
+        &LatLng::new(0.0, 6.0 * zone as f64 - 183.0),
+        UTM_K0,
+        (Meter(UTM_FALSE_EASTING), Meter(false_northing)),
+    )
+}
+
+fn ups_projection(ellipsoid: &Ellipsoid) -> Result<PolarStereographic, GeodesyError> {
+    PolarStereographic::new(
+        ellipsoid,
+        UPS_K0,
+        (Meter(UPS_FALSE_ORIGIN), Meter(UPS_FALSE_ORIGIN)),
+    )
+}
+
+/// The position on the UTM or UPS grid in its standard zone.
+///
+/// ```
+/// # use auxillary_sphere::{
+/// #     earth::ellipsoid::WGS84,
+/// #     geodesy::{latlng::LatLng, projection::utm::*},
+/// # };
+/// let eiffel = to_utm(&WGS84, &LatLng::new(48.85826, 2.29451)).unwrap();
+/// assert_eq!(eiffel.to_string(), "31U 448253 5411939");
+///
+/// // Bergen, west of 6°, is in zone 32 by the exception for Norway.
+/// let bergen = to_utm(&WGS84, &LatLng::new(60.39, 5.32)).unwrap();
+/// assert_eq!(bergen.zone, Zone::Utm(32));
+///
+/// let pole = to_utm(&WGS84, &LatLng::new(90.0, 0.0)).unwrap();
+/// assert_eq!(pole.to_string(), "Z 2000000 2000000");
+/// ```
+pub fn to_utm(ellipsoid: &Ellipsoid, x: &LatLng) -> Result<Utm, GeodesyError> {
+    let x = &LatLng {
+        lat: check_lat(x.lat)?,
+        lng: plus_minus_pi_rad(x.lng),
+    };
+    let Deg(lat) = rad_to_deg(x.lat);
+    let Deg(lng) = rad_to_deg(x.lng);
+    let zone = standard_zone(lat, lng);
+    let hemisphere = if lat >= 0.0 {
+        Hemisphere::North
+    } else {
+        Hemisphere::South
+    };
+
+    let grid = match zone {
+        Zone::Utm(z) => utm_projection(ellipsoid, z, hemisphere)?.forward(x)?,
+        Zone::Ups => ups_projection(ellipsoid)?.forward(hemisphere, x)?,
+    };
+
+    Ok(Utm {
+        zone,
+        band: band(lat, lng),
+        hemisphere,
+        easting: grid.easting,
+        northing: grid.northing,
+        convergence: grid.convergence,
+        scale: grid.scale,
+    })
+}
+
+/// The position of coordinates on the UTM or UPS grid, with a UTM zone
+/// rejected outside 1 .. 60.
+pub fn from_utm(
+    ellipsoid: &Ellipsoid,
+    zone: Zone,
+    hemisphere: Hemisphere,
+    easting: Meter,
+    northing: Meter,
+) -> Result<Unprojected, GeodesyError> {
+    Ok(match zone {
+        Zone::Utm(z) => utm_projection(ellipsoid, z, hemisphere)?.reverse(easting, northing),
+        Zone::Ups => ups_projection(ellipsoid)?.reverse(hemisphere, easting, northing),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::earth::ellipsoid::WGS84;
+
+    #[test]
+    fn zones() {
+        for ((lat, lng), expected) in [
+            ((0.0, -180.0), Zone::Utm(1)),
+            ((0.0, 180.0), Zone::Utm(1)),
+            ((0.0, 179.9), Zone::Utm(60)),
+            ((-33.9, 151.2), Zone::Utm(56)),
+            ((56.0, 3.0), Zone::Utm(32)),
+            ((55.9, 3.0), Zone::Utm(31)),
+            ((60.0, 2.9), Zone::Utm(31)),
+            ((64.0, 3.0), Zone::Utm(31)),
+            ((78.2, 8.9), Zone::Utm(31)),
+            ((78.2, 9.0), Zone::Utm(33)),
+            ((78.2, 20.9), Zone::Utm(33)),
+            ((78.2, 21.0), Zone::Utm(35)),
+            ((78.2, 33.0), Zone::Utm(37)),
+            ((78.2, 42.0), Zone::Utm(38)),
+            ((71.9, 9.0), Zone::Utm(32)),
+            ((83.9, 0.0), Zone::Utm(31)),
+            ((84.0, 0.0), Zone::Ups),
+            ((-80.0, 0.0), Zone::Utm(31)),
+            ((-80.1, 0.0), Zone::Ups),
+        ] {
+            assert_eq!(
+                zone(&LatLng::new(lat, lng)).unwrap(),
+                expected,
+                "{lat}, {lng}"
+            );
+        }
+    }
+
+    #[test]
+    fn bands() {
+        for ((lat, lng), expected) in [
+            ((-80.0, 0.0), 'C'),
+            ((-0.1, 0.0), 'M'),
+            ((0.0, 0.0), 'N'),
+            ((72.0, 0.0), 'X'),
+            ((83.9, 0.0), 'X'),
+            ((85.0, -1.0), 'Y'),
+            ((85.0, 1.0), 'Z'),
+            ((-85.0, -1.0), 'A'),
+            ((-85.0, 1.0), 'B'),
+        ] {
+            let utm = to_utm(&WGS84, &LatLng::new(lat, lng)).unwrap();
+            assert_eq!(utm.band, expected, "{lat}, {lng}");
+        }
+    }
+
+    #[test]
+    fn normalized_latitude() {
+        // A latitude a turn past 10°, normalized before the zone, hemisphere
+        // and band are taken from it.
+        let past = to_utm(&WGS84, &LatLng::new(370.0, 9.0)).unwrap();
+        let utm = to_utm(&WGS84, &LatLng::new(10.0, 9.0)).unwrap();
+        assert_eq!(past.to_string(), utm.to_string());
+        assert_eq!(past.hemisphere, Hemisphere::North);
+        assert_eq!(past.to_string(), "32P 500000 1105412");
+    }
+
+    #[test]
+    fn round_trip() {
+        for x in [
+            LatLng::new(48.85826, 2.29451),
+            LatLng::new(-33.85678, 151.21530),
+            LatLng::new(-0.5, -78.5),
+            LatLng::new(78.22, 15.65),
+            LatLng::new(-89.0, 120.0),
+            LatLng::new(87.0, -45.0),
+        ] {
+            let utm = to_utm(&WGS84, &x).unwrap();
+            let back =
+                from_utm(&WGS84, utm.zone, utm.hemisphere, utm.easting, utm.northing).unwrap();
+
+            assert!((back.x.lat.0 - x.lat.0).abs() < 1e-14, "{x}");
+            assert!((back.x.lng.0 - x.lng.0).abs() < 1e-14, "{x}");
+            assert!(
+                (back.convergence.0 - utm.convergence.0).abs() < 1e-14,
+                "{x}"
+            );
+            assert!((back.scale - utm.scale).abs() < 1e-14, "{x}");
+        }
+    }
+
+    #[test]
+    fn invalid_zone() {
+        for zone in [0, 61] {
+            let result = from_utm(
+                &WGS84,
+                Zone::Utm(zone),
+                Hemisphere::North,
+                Meter(500000.0),
+                Meter(0.0),
+            );
+            assert_eq!(result.unwrap_err(), GeodesyError::InvalidProjection);
+        }
+    }
+}
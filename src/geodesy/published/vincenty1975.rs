@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use std::{f64::consts::PI, fmt::Display};
 
 // Test data from ...
 //
@@ -11,7 +11,8 @@ use crate::{
     geodesy::{
         latlng::LatLng,
         point_to_point,
-        problems::{Az, Dist, InverseProblem, InverseSolution},
+        problems::{Az, DirectProblem, DirectSolution, Dist, InverseProblem, InverseSolution},
+        vincenty::{self, GeodeticAccuracy},
     },
     units::{
         convert::{deg_to_rad, rad_to_deg},
@@ -247,23 +248,48 @@ pub fn inverse_solutions() -> Vec<InverseSolution> {
             az2: Some(Az {
                 az: deg_to_rad(y_azimuth.to_deg()).0,
             }),
+            iterations: 0,
+            aux: None,
         })
         .collect()
 }
 
-pub fn direct_pairs() -> Vec<(InverseProblem, InverseSolution)> {
-    inverse_problems()
-        .into_iter()
-        .zip(inverse_solutions())
+// The direct problems start from the same points, with the forward azimuths
+// and distances of the inverse solutions, arriving at the same end points.
+pub fn direct_problems() -> Vec<DirectProblem> {
+    INVERSE_PROBLEM_DATA
+        .iter()
+        .zip(X_AZIMUTHS.iter().zip(DISTANCES.iter()))
+        .map(|((x, _), (&x_azimuth, &distance))| DirectProblem {
+            x: (*x).into(),
+            az1: Az {
+                az: deg_to_rad(x_azimuth.to_deg()).0,
+            },
+            s: Dist { dist: distance },
+        })
         .collect()
 }
 
-pub fn direct_problems() -> Vec<InverseProblem> {
-    direct_pairs().into_iter().map(|(p, _)| p).collect()
+pub fn direct_solutions() -> Vec<DirectSolution> {
+    INVERSE_PROBLEM_DATA
+        .iter()
+        .zip(Y_AZIMUTHS.iter())
+        .map(|((_, y), &y_azimuth)| DirectSolution {
+            y: (*y).into(),
+            az2: Some(Az {
+                az: deg_to_rad(y_azimuth.to_deg()).0,
+            }),
+            iterations: 0,
+            aux: None,
+        })
+        .collect()
 }
 
-pub fn direct_solutions() -> Vec<InverseSolution> {
-    direct_pairs().into_iter().map(|(_, s)| s).collect()
+pub fn direct_pairs() -> Vec<(DirectProblem, DirectSolution)> {
+    direct_problems()
+        .into_iter()
+        .zip(direct_solutions())
+        .collect()
 }
 
 // Units of mm.
@@ -279,14 +305,23 @@ pub const AZ_TOLERANCE: DMS = DMS {
     sec: 0.016667,
 };
 
+// The tables are given to 0.00001" and Vincenty's direct solutions of latitude,
+// longitude and azimuth agree with them to within a few of those.
+pub const DIRECT_TOLERANCE: DMS = DMS {
+    deg: 0,
+    min: 0,
+    sec: 0.00005,
+};
+
 // Units of kilometers for distance and tolerance.
 pub type TestTolerance = Dist;
 
 pub type DiffDMS = fn(DMS, DMS) -> DMS;
 pub type AzTolerance = DMS;
-pub type SpanLatLng = fn(LatLng, LatLng) -> Dist;
-pub type AzimuthFwd = fn(LatLng, LatLng) -> Option<Rad>;
-pub type AzimuthBwd = fn(LatLng, LatLng) -> Option<Rad>;
+pub type SpanLatLng = fn(&Ellipsoid, LatLng, LatLng) -> Dist;
+pub type AzimuthFwd = fn(&Ellipsoid, LatLng, LatLng) -> Option<Rad>;
+pub type AzimuthBwd = fn(&Ellipsoid, LatLng, LatLng) -> Option<Rad>;
+pub type DirectLatLng = fn(&Ellipsoid, &DirectProblem) -> Option<DirectSolution>;
 
 pub fn describe_inverse_distance(
     x: LatLng,
@@ -294,7 +329,19 @@ pub fn describe_inverse_distance(
     s_expected: Dist,
     tolerance: TestTolerance,
 ) -> String {
-    format!("{} to {} = {} ± {}", x, y, s_expected, tolerance)
+    format!("{} to {} = {} ± {:.6}", x, y, s_expected, tolerance.dist)
+}
+
+pub fn describe_direct(
+    DirectProblem { x, az1, s }: &DirectProblem,
+    field: &str,
+    expected: DMS,
+    tolerance: AzTolerance,
+) -> String {
+    format!(
+        "{} at {} for {} -> {} {} ± {}",
+        x, az1, s, field, expected, tolerance
+    )
 }
 
 pub fn describe_azimuth_fwd(
@@ -415,6 +462,7 @@ pub fn inverse_checks(
     diff_az_rev: fn(DMS, DMS) -> DMS,
     dist_tolerances: &[TestTolerance],
     az_tolerance: AzTolerance,
+    ellipsoids: &[Ellipsoid],
     spans: Vec<SpanLatLng>,
     az_fwds: Vec<AzimuthFwd>,
     az_revs: Vec<AzimuthBwd>,
@@ -422,16 +470,17 @@ pub fn inverse_checks(
     probs: &[InverseProblem],
 ) -> Vec<Assertion> {
     let f = |dist_tolerance: &TestTolerance,
+             ellipsoid: &Ellipsoid,
              span: &SpanLatLng,
              az_fwd: &AzimuthFwd,
              az_rev: &AzimuthBwd,
              soln: &InverseSolution,
              prob: &InverseProblem| {
-        let InverseSolution { s, az1, az2 } = soln;
+        let InverseSolution { s, az1, az2, .. } = soln;
         let InverseProblem { x, y } = prob;
-        let s_prime = span(*x, *y);
-        let az1_prime = az_fwd(*x, *y);
-        let az2_prime = az_rev(*x, *y);
+        let s_prime = span(ellipsoid, *x, *y);
+        let az1_prime = az_fwd(ellipsoid, *x, *y);
+        let az2_prime = az_rev(ellipsoid, *x, *y);
 
         vec![
             {
@@ -500,50 +549,185 @@ pub fn inverse_checks(
 
     dist_tolerances
         .iter()
-        .zip(spans.iter())
+        .zip(ellipsoids.iter().zip(spans.iter()))
         .zip(az_fwds.iter().zip(az_revs.iter()))
         .zip(solns.iter().zip(probs.iter()))
         .flat_map(
-            |(((dist_tolerance, span), (az_fwd, az_rev)), (soln, prob))| {
-                f(dist_tolerance, span, az_fwd, az_rev, soln, prob)
+            |(((dist_tolerance, (ellipsoid, span)), (az_fwd, az_rev)), (soln, prob))| {
+                f(dist_tolerance, ellipsoid, span, az_fwd, az_rev, soln, prob)
             },
         )
         .collect()
 }
 
-pub fn vincenty_units() -> Result<(), String> {
-    let diff_az_fwd: DiffDMS = |x, y| DMS::abs_diff_dms(x, y);
-    let diff_az_rev: DiffDMS = |x, y| DMS::abs_diff_dms_180(y)(x);
+pub fn direct_checks(
+    diff_az: DiffDMS,
+    lat_lng_tolerance: AzTolerance,
+    az_tolerance: AzTolerance,
+    ellipsoids: &[Ellipsoid],
+    directs: Vec<DirectLatLng>,
+    solns: &[DirectSolution],
+    probs: &[DirectProblem],
+) -> Vec<Assertion> {
+    let f = |ellipsoid: &Ellipsoid,
+             direct: &DirectLatLng,
+             soln: &DirectSolution,
+             prob: &DirectProblem| {
+        let DirectSolution { y, az2, .. } = soln;
+        let Some(DirectSolution {
+            y: y_prime,
+            az2: az2_prime,
+            ..
+        }) = direct(ellipsoid, prob)
+        else {
+            return vec![assert_failure(&format!("{} has no solution", prob))];
+        };
+
+        let check = |field: &str, expected: DMS, actual: DMS, tolerance: AzTolerance| {
+            let diff = diff_az(expected, actual);
+            test_case(
+                &describe_direct(prob, field, expected, tolerance),
+                assert_compare_with(
+                    |dms| dms.to_deg().0,
+                    "",
+                    |a, b| a <= b,
+                    "<=",
+                    &tolerance,
+                    &diff,
+                ),
+            )
+        };
 
-    fn span_lat_lng(x: LatLng, y: LatLng) -> Dist {
-        let vincenty_distance = point_to_point::vincenty::distance;
-        match vincenty_distance(BESSEL, x, y) {
-            Ok(d) => d,
-            Err(_) => Dist { dist: 0.0 },
-        }
+        vec![
+            check(
+                "lat",
+                rad_to_dms(y.lat),
+                rad_to_dms(y_prime.lat),
+                lat_lng_tolerance,
+            ),
+            check(
+                "lng",
+                rad_to_dms(y.lng),
+                rad_to_dms(y_prime.lng),
+                lat_lng_tolerance,
+            ),
+            match (az2, az2_prime) {
+                (Some(az2), Some(az2_prime)) => {
+                    check("az2", az_to_dms(*az2), az_to_dms(az2_prime), az_tolerance)
+                }
+                _ => Ok(()),
+            },
+        ]
+    };
+
+    ellipsoids
+        .iter()
+        .zip(directs.iter())
+        .zip(solns.iter().zip(probs.iter()))
+        .flat_map(|((ellipsoid, direct), (soln, prob))| f(ellipsoid, direct, soln, prob))
+        .collect()
+}
+
+fn vincenty_direct(ellipsoid: &Ellipsoid, prob: &DirectProblem) -> Option<DirectSolution> {
+    vincenty::direct(ellipsoid, &GeodeticAccuracy::default(), prob).ok()
+}
+
+fn vincenty_span(ellipsoid: &Ellipsoid, x: LatLng, y: LatLng) -> Dist {
+    // A distance that can't be calculated fails its check.
+    match point_to_point::vincenty::distance(*ellipsoid, x, y) {
+        Ok(d) => d,
+        Err(_) => Dist { dist: f64::NAN },
     }
+}
+
+fn vincenty_inverse(ellipsoid: &Ellipsoid, x: LatLng, y: LatLng) -> Option<InverseSolution> {
+    point_to_point::vincenty::inverse(
+        ellipsoid,
+        &GeodeticAccuracy::default(),
+        &InverseProblem { x, y },
+    )
+    .ok()
+}
+
+fn vincenty_az_fwd(ellipsoid: &Ellipsoid, x: LatLng, y: LatLng) -> Option<Rad> {
+    vincenty_inverse(ellipsoid, x, y).map(|soln| Rad(soln.az1.az))
+}
+
+// The reverse azimuth, at the end point looking back to the start point.
+fn vincenty_az_rev(ellipsoid: &Ellipsoid, x: LatLng, y: LatLng) -> Option<Rad> {
+    vincenty_inverse(ellipsoid, x, y)
+        .and_then(|soln| soln.az2)
+        .map(|az2| Rad(az2.az).rotate(Rad(PI)).normalize())
+}
+
+fn vincenty_inverse_checks() -> Vec<Assertion> {
+    let diff_az_fwd: DiffDMS = |x, y| DMS::abs_diff_dms(x, y);
+    let diff_az_rev: DiffDMS = |x, y| DMS::abs_diff_dms_180(y)(x);
 
-    let dist_tolerances: &[Dist] = &DISTANCES
+    let dist_tolerances: &[Dist] = &INDIRECT_DISTANCE_TOLERANCES
         .iter()
         .map(|&d| Dist { dist: d })
         .collect::<Vec<_>>();
 
-    // TODO: Add direct checks to checks
-    let checks = inverse_checks(
+    inverse_checks(
         diff_az_fwd,
         diff_az_rev,
         dist_tolerances,
         AZ_TOLERANCE,
-        vec![span_lat_lng; 5],
-        vec![|_, _| None; 5],
-        vec![|_, _| None; 5],
+        ELLIPSOIDS,
+        vec![vincenty_span; 5],
+        vec![vincenty_az_fwd; 5],
+        vec![vincenty_az_rev; 5],
         &inverse_solutions(),
         &inverse_problems(),
-    );
+    )
+}
+
+fn vincenty_direct_checks() -> Vec<Assertion> {
+    direct_checks(
+        DMS::abs_diff_dms,
+        DIRECT_TOLERANCE,
+        DIRECT_TOLERANCE,
+        ELLIPSOIDS,
+        vec![vincenty_direct; 5],
+        &direct_solutions(),
+        &direct_problems(),
+    )
+}
 
-    for check in checks {
+pub fn vincenty_units() -> Result<(), String> {
+    let inverse = vincenty_inverse_checks();
+
+    let direct = vincenty_direct_checks();
+
+    for check in inverse.into_iter().chain(direct) {
         check?;
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inverse() {
+        let checks = vincenty_inverse_checks();
+
+        assert_eq!(checks.len(), 15);
+        for check in checks {
+            check.unwrap();
+        }
+    }
+
+    #[test]
+    fn direct() {
+        let checks = vincenty_direct_checks();
+
+        assert_eq!(checks.len(), 15);
+        for check in checks {
+            check.unwrap();
+        }
+    }
+}
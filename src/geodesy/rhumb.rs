@@ -0,0 +1,272 @@
+// Rhumb lines, or loxodromes, the paths of constant azimuth that cross every
+// meridian at the same angle.
+//
+// On a Mercator projection a rhumb line is straight, so with the isometric
+// latitude, ψ, as the northing, the azimuth is given by tan α = Δλ / Δψ.
+// The distance along a rhumb line is the distance along the meridian, the
+// meridian arc, divided by cos α, which is in proportion to the rectifying
+// latitude.
+//
+// SEE: <https://arxiv.org/abs/1102.1215> and
+// <https://www.movable-type.co.uk/scripts/latlong.html#rhumblines>
+use crate::{
+    earth::{ellipsoid::Ellipsoid, sphere::Sphere},
+    units::{convert::plus_minus_pi_rad, Angle, Meter, Rad, Radius},
+};
+use std::f64::consts::FRAC_PI_2;
+
+use super::{
+    error::GeodesyError,
+    latitude::{AuxiliaryLatitudes, Latitude},
+    latlng::LatLng,
+    problems::{Az, DirectProblem, DirectSolution, Dist, InverseProblem, InverseSolution},
+    vincenty::check_lat,
+};
+
+// Below this difference in isometric latitude, the line is taken as running
+// along a parallel.
+const EPSILON: f64 = 1e-12;
+
+// The constants of an ellipsoid used by rhumb lines.
+struct Rhumb {
+    lats: AuxiliaryLatitudes,
+    a: f64,
+    e2: f64,
+    // The rectifying radius, the radius of the sphere with the same meridian
+    // length as the ellipsoid.
+    r: f64,
+}
+
+impl Rhumb {
+    fn new(ellipsoid: &Ellipsoid) -> Result<Self, GeodesyError> {
+        let lats = AuxiliaryLatitudes::new(ellipsoid)?;
+        let Radius(Meter(a)) = ellipsoid.equatorial_r;
+        let Meter(q) = ellipsoid.quarter_meridian();
+
+        Ok(Rhumb {
+            lats,
+            a,
+            e2: ellipsoid.eccentricity_sq(),
+            r: q / FRAC_PI_2,
+        })
+    }
+
+    // The isometric latitude, taken from the tangent so that it is large but
+    // finite at the poles, where the lines meet.
+    fn isometric_lat(&self, phi: f64) -> f64 {
+        self.lats.taupf(phi.tan()).asinh()
+    }
+
+    // The length of the meridian from the equator to the latitude.
+    fn meridian_arc(&self, phi: f64) -> f64 {
+        self.r * self.lats.geodetic_to(phi, Latitude::Rectifying)
+    }
+
+    // The latitude reached by a length along the meridian from the equator.
+    fn footpoint_lat(&self, m: f64) -> f64 {
+        self.lats.geodetic_from(m / self.r, Latitude::Rectifying)
+    }
+
+    // The radius of the parallel, N cos φ.
+    fn parallel_r(&self, phi: f64) -> f64 {
+        self.a * phi.cos() / (1.0 - self.e2 * phi.sin() * phi.sin()).sqrt()
+    }
+
+    // The ratio of the change in meridian arc to the change in isometric
+    // latitude between the two latitudes, the radius of the parallel in the
+    // limit of the line running along a parallel.
+    fn ratio(&self, (phi1, psi1, m1): (f64, f64, f64), (psi2, m2): (f64, f64)) -> f64 {
+        let d_psi = psi2 - psi1;
+        if d_psi.abs() < EPSILON {
+            self.parallel_r(phi1)
+        } else {
+            (m2 - m1) / d_psi
+        }
+    }
+}
+
+fn sphere_ellipsoid(sphere: &Sphere) -> Ellipsoid {
+    Ellipsoid {
+        equatorial_r: sphere.radius,
+        recip_f: f64::INFINITY,
+    }
+}
+
+/// The solution to the inverse problem along the rhumb line on an ellipsoid,
+/// taking the shorter way around in longitude. The azimuth, normalized to 0° ..
+/// 360°, is the same at both points.
+///
+/// ```
+/// # use auxillary_sphere::{
+/// #     earth::ellipsoid::WGS84,
+/// #     geodesy::{latlng::LatLng, problems::*, rhumb, point_to_point::vincenty},
+/// # };
+/// let x = LatLng::new(51.5007, -0.1246);
+/// let y = LatLng::new(40.6892, -74.0445);
+/// let rhumb = rhumb::inverse(&WGS84, &InverseProblem { x, y }).unwrap();
+/// let geodesic =
+///     vincenty::inverse(&WGS84, &Default::default(), &InverseProblem { x, y }).unwrap();
+///
+/// // Keeping to a constant azimuth makes for a longer journey.
+/// assert!(rhumb.s.dist > geodesic.s.dist);
+/// assert_eq!(rhumb.az1.az, rhumb.az2.unwrap().az);
+/// ```
+pub fn inverse(
+    ellipsoid: &Ellipsoid,
+    prob: &InverseProblem,
+) -> Result<InverseSolution, GeodesyError> {
+    let rhumb = Rhumb::new(ellipsoid)?;
+    let Rad(phi1) = check_lat(prob.x.lat)?;
+    let Rad(phi2) = check_lat(prob.y.lat)?;
+    let Rad(d_lng) = plus_minus_pi_rad(Rad(prob.y.lng.0 - prob.x.lng.0));
+
+    let psi1 = rhumb.isometric_lat(phi1);
+    let psi2 = rhumb.isometric_lat(phi2);
+    let m1 = rhumb.meridian_arc(phi1);
+    let m2 = rhumb.meridian_arc(phi2);
+    let q = rhumb.ratio((phi1, psi1, m1), (psi2, m2));
+
+    let az = Az {
+        az: Rad(d_lng.atan2(psi2 - psi1)).normalize().0,
+    };
+
+    Ok(InverseSolution {
+        s: Dist {
+            dist: (m2 - m1).hypot(q * d_lng),
+        },
+        az1: az,
+        az2: Some(az),
+        iterations: 0,
+        aux: None,
+    })
+}
+
+/// The solution to the direct problem along the rhumb line on an ellipsoid. A
+/// rhumb line that isn't along a parallel spirals in towards a pole without
+/// reaching it, so a distance going beyond the pole is an error.
+pub fn direct(ellipsoid: &Ellipsoid, prob: &DirectProblem) -> Result<DirectSolution, GeodesyError> {
+    let rhumb = Rhumb::new(ellipsoid)?;
+    let Rad(phi1) = check_lat(prob.x.lat)?;
+    let az = Rad(prob.az1.az).normalize().0;
+    let s = prob.s.dist;
+
+    let m1 = rhumb.meridian_arc(phi1);
+    let m2 = m1 + s * az.cos();
+    if m2.abs() > rhumb.r * FRAC_PI_2 {
+        return Err(GeodesyError::BeyondPole);
+    }
+
+    let phi2 = rhumb.footpoint_lat(m2);
+    let psi1 = rhumb.isometric_lat(phi1);
+    let psi2 = rhumb.isometric_lat(phi2);
+    let q = rhumb.ratio((phi1, psi1, m1), (psi2, m2));
+    let d_lng = s * az.sin() / q;
+
+    Ok(DirectSolution {
+        y: LatLng {
+            lat: Rad(phi2),
+            lng: plus_minus_pi_rad(Rad(prob.x.lng.0 + d_lng)),
+        },
+        az2: Some(Az { az }),
+        iterations: 0,
+        aux: None,
+    })
+}
+
+/// The solution to the inverse problem along the rhumb line on a sphere.
+pub fn inverse_on(sphere: &Sphere, prob: &InverseProblem) -> Result<InverseSolution, GeodesyError> {
+    inverse(&sphere_ellipsoid(sphere), prob)
+}
+
+/// The solution to the direct problem along the rhumb line on a sphere.
+pub fn direct_on(sphere: &Sphere, prob: &DirectProblem) -> Result<DirectSolution, GeodesyError> {
+    direct(&sphere_ellipsoid(sphere), prob)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        earth::{ellipsoid::WGS84, sphere::FAI_SPHERE},
+        units::{
+            convert::{deg_to_rad, rad_to_deg},
+            Deg, DMS,
+        },
+    };
+
+    #[test]
+    fn dover_calais() {
+        // SEE: https://www.movable-type.co.uk/scripts/latlong.html#rhumblines
+        let x = LatLng::from((
+            DMS {
+                deg: 51,
+                min: 7,
+                sec: 32.0,
+            },
+            DMS {
+                deg: 1,
+                min: 20,
+                sec: 17.0,
+            },
+        ));
+        let y = LatLng::from((
+            DMS {
+                deg: 50,
+                min: 57,
+                sec: 48.0,
+            },
+            DMS {
+                deg: 1,
+                min: 51,
+                sec: 9.0,
+            },
+        ));
+        let soln = inverse_on(&FAI_SPHERE, &InverseProblem { x, y }).unwrap();
+        let az = DMS::from_deg(rad_to_deg(Rad(soln.az1.az)));
+
+        assert_eq!((az.deg, az.min), (116, 38));
+        assert!((az.sec - 10.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn meridian_and_equator() {
+        let origin = LatLng::new(0.0, 0.0);
+        let pole = LatLng::new(90.0, 0.0);
+        let soln = inverse(&WGS84, &InverseProblem { x: origin, y: pole }).unwrap();
+        let Meter(q) = WGS84.quarter_meridian();
+        assert!((soln.s.dist - q).abs() < 1e-4);
+        assert_eq!(soln.az1.az, 0.0);
+
+        let y = LatLng::new(0.0, 90.0);
+        let soln = inverse(&WGS84, &InverseProblem { x: origin, y }).unwrap();
+        let Radius(Meter(a)) = WGS84.equatorial_r;
+        assert!((soln.s.dist - a * FRAC_PI_2).abs() < 1e-6);
+        assert!((soln.az1.az - FRAC_PI_2).abs() < 1e-15);
+    }
+
+    #[test]
+    fn round_trip() {
+        let x = LatLng::new(-33.8688, 151.2093);
+        for az in (0..360).step_by(15) {
+            let az1 = Az {
+                az: deg_to_rad(Deg(az as f64 + 0.5)).0,
+            };
+            let s = Dist { dist: 5_000_000.0 };
+            let d = direct(&WGS84, &DirectProblem { x, az1, s }).unwrap();
+            let i = inverse(&WGS84, &InverseProblem { x, y: d.y }).unwrap();
+
+            assert!((i.s.dist - s.dist).abs() < 1e-6);
+            assert!((i.az1.az - az1.az).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn beyond_pole() {
+        let prob = DirectProblem {
+            x: LatLng::new(80.0, 0.0),
+            az1: Az { az: 0.1 },
+            s: Dist { dist: 2_000_000.0 },
+        };
+        assert_eq!(direct(&WGS84, &prob).unwrap_err(), GeodesyError::BeyondPole);
+    }
+}
@@ -0,0 +1,586 @@
+use crate::{
+    earth::{ellipsoid::Ellipsoid, sphere::Sphere},
+    units::{Meter, Radius},
+};
+
+use super::{
+    error::GeodesyError,
+    haversines, intersection, karney,
+    latlng::LatLng,
+    point_to_point,
+    problems::{
+        DirectProblem, DirectSolution, Dist, Intersection, IntersectionProblem, InverseProblem,
+        InverseSolution, TrackProblem, TrackSolution,
+    },
+    vincenty::{self, check_lat, GeodesicLine, GeodeticAccuracy},
+};
+
+/// Solves the direct and inverse geodesy problems on some model of the earth,
+/// letting the model be swapped without changing call sites.
+///
+/// ```
+/// # use auxillary_sphere::{
+/// #     earth::{ellipsoid::WGS84, sphere::Sphere},
+/// #     geodesy::{latlng::LatLng, problems::InverseProblem, solver::*},
+/// # };
+/// fn span(solver: &impl GeodesicSolver, x: LatLng, y: LatLng) -> f64 {
+///     solver.inverse(&InverseProblem { x, y }).unwrap().s.dist
+/// }
+///
+/// let x = LatLng::new(51.5007, -0.1246);
+/// let y = LatLng::new(40.6892, -74.0445);
+/// let on_sphere = span(&SphereSolver::new(Sphere::mean(&WGS84)), x, y);
+/// let on_ellipsoid = span(&EllipsoidSolver::new(WGS84), x, y);
+///
+/// assert!((on_sphere - on_ellipsoid).abs() < 20000.0);
+/// ```
+pub trait GeodesicSolver {
+    fn direct(&self, prob: &DirectProblem) -> Result<DirectSolution, GeodesyError>;
+    fn inverse(&self, prob: &InverseProblem) -> Result<InverseSolution, GeodesyError>;
+
+    /// The radius of the sphere, or the mean radius, `R₁`, of the ellipsoid,
+    /// for taking distances to arcs in the iterations for tracks and
+    /// intersections.
+    fn radius(&self) -> Radius;
+
+    /// The cross-track and along-track distances of a point from the geodesic
+    /// through two others and the point on that geodesic closest to it.
+    ///
+    /// From a start at x, the closest point is found by moving along the track
+    /// by the along-track distance of the right spherical triangle made with
+    /// the point, repeating until the move is less than a micrometre.
+    ///
+    /// SEE: Baselga, S., Martínez-Llario, J.C., Intersection and point-to-line
+    /// solutions for geodesics on the ellipsoid, Stud Geophys Geod 62, 353–363
+    /// (2018), <https://doi.org/10.1007/s11200-017-1020-z>
+    fn track(&self, prob: &TrackProblem) -> Result<TrackSolution, GeodesyError> {
+        let Radius(Meter(r)) = self.radius();
+        let TrackProblem { x, y, p } = *prob;
+        let az1 = self.inverse(&InverseProblem { x, y })?.az1;
+
+        let mut along = 0.0;
+        for i in 1..=TRACK_MAX_ITERATIONS {
+            let DirectSolution { y: a, az2, .. } = self.direct(&DirectProblem {
+                x,
+                az1,
+                s: Dist { dist: along },
+            })?;
+            let az_track = az2.map_or(az1.az, |az| az.az);
+            let InverseSolution { s, az1: az_p, .. } =
+                self.inverse(&InverseProblem { x: a, y: p })?;
+
+            let angle = az_p.az - az_track;
+            let s_r = s.dist / r;
+            let step = r * (s_r.sin() * angle.cos()).atan2(s_r.cos());
+            if step.abs() < TRACK_TOLERANCE {
+                return Ok(TrackSolution {
+                    cross_track: Dist {
+                        dist: s.dist.copysign(angle.sin()),
+                    },
+                    along_track: Dist { dist: along },
+                    closest: a,
+                    iterations: i,
+                });
+            }
+
+            along += step;
+        }
+
+        Err(GeodesyError::IterationLimit(TRACK_MAX_ITERATIONS))
+    }
+
+    /// Where two geodesics cross, as with [intersection::intersection].
+    fn intersection(&self, prob: &IntersectionProblem) -> Result<Intersection, GeodesyError> {
+        intersection::intersection(self, prob)
+    }
+
+    /// The point half way along the geodesic between the points.
+    fn midpoint(&self, prob: &InverseProblem) -> Result<LatLng, GeodesyError> {
+        let points = self.intermediate(prob, Spacing::Count(1))?;
+        Ok(points.first().copied().unwrap_or(prob.x))
+    }
+
+    /// The points along the geodesic between the points, not including them,
+    /// spaced evenly or at a fixed distance apart from the first. A spacing
+    /// giving more than [MAX_POINTS] points is rejected.
+    ///
+    /// ```
+    /// # use auxillary_sphere::{
+    /// #     earth::ellipsoid::WGS84,
+    /// #     geodesy::{latlng::LatLng, problems::*, solver::*},
+    /// # };
+    /// let solver = EllipsoidSolver::new(WGS84);
+    /// let prob = InverseProblem {
+    ///     x: LatLng::new(51.5007, -0.1246),
+    ///     y: LatLng::new(40.6892, -74.0445),
+    /// };
+    /// let span = |x, y| solver.inverse(&InverseProblem { x, y }).unwrap().s.dist;
+    ///
+    /// // The one point of an even spacing is half way.
+    /// let evenly = solver.intermediate(&prob, Spacing::Count(1)).unwrap();
+    /// assert!((span(prob.x, evenly[0]) - span(evenly[0], prob.y)).abs() < 1e-4);
+    ///
+    /// // Points every 100 km, the last within 100 km of the end.
+    /// let every = solver.intermediate(&prob, Spacing::Every(Dist { dist: 100000.0 })).unwrap();
+    /// assert_eq!(every.len(), 55);
+    /// assert!((span(prob.x, every[0]) - 100000.0).abs() < 1e-4);
+    /// for pair in every.windows(2) {
+    ///     assert!((span(pair[0], pair[1]) - 100000.0).abs() < 1e-4);
+    /// }
+    /// assert!(span(every[54], prob.y) < 100000.0);
+    /// ```
+    fn intermediate(
+        &self,
+        prob: &InverseProblem,
+        spacing: Spacing,
+    ) -> Result<Vec<LatLng>, GeodesyError> {
+        let InverseSolution { s, az1, .. } = self.inverse(prob)?;
+        spacing
+            .distances(s)?
+            .into_iter()
+            .map(|s| {
+                self.direct(&DirectProblem { x: prob.x, az1, s })
+                    .map(|soln| soln.y)
+            })
+            .collect()
+    }
+}
+
+/// How to space points along a geodesic.
+#[derive(Debug, Clone, Copy)]
+pub enum Spacing {
+    /// This many points, evenly spaced.
+    Count(usize),
+    /// Points this distance apart, from the start. A spacing that isn't
+    /// positive gives no points.
+    Every(Dist),
+}
+
+/// The most points that a [Spacing] may give along a geodesic.
+pub const MAX_POINTS: usize = 1_000_000;
+
+impl Spacing {
+    // The distances from the start of a geodesic of length s to each of the
+    // points between its ends, rejected when there would be more than
+    // MAX_POINTS of them.
+    fn distances(&self, Dist { dist: s }: Dist) -> Result<Vec<Dist>, GeodesyError> {
+        match *self {
+            Spacing::Count(n) if n > MAX_POINTS => Err(GeodesyError::TooManyPoints(MAX_POINTS)),
+            Spacing::Count(n) => Ok((1..=n)
+                .map(|i| Dist {
+                    dist: s * i as f64 / (n + 1) as f64,
+                })
+                .collect()),
+            Spacing::Every(Dist { dist: k }) if k > 0.0 => {
+                if s / k > MAX_POINTS as f64 {
+                    return Err(GeodesyError::TooManyPoints(MAX_POINTS));
+                }
+                Ok((1..)
+                    .map(|i| i as f64 * k)
+                    .take_while(|&d| d < s)
+                    .map(|dist| Dist { dist })
+                    .collect())
+            }
+            Spacing::Every(_) => Ok(vec![]),
+        }
+    }
+}
+
+// The along-track move, in metres, below which the closest point is found.
+const TRACK_TOLERANCE: f64 = 1e-6;
+const TRACK_MAX_ITERATIONS: usize = 50;
+
+/// Solves on a sphere with haversines.
+#[derive(Debug, Clone, Copy)]
+pub struct SphereSolver {
+    pub sphere: Sphere,
+}
+
+impl SphereSolver {
+    pub fn new(sphere: Sphere) -> Self {
+        SphereSolver { sphere }
+    }
+}
+
+/// Solves on an ellipsoid with Vincenty's method.
+#[derive(Clone, Copy)]
+pub struct EllipsoidSolver {
+    pub ellipsoid: Ellipsoid,
+    pub accuracy: GeodeticAccuracy,
+}
+
+impl EllipsoidSolver {
+    pub fn new(ellipsoid: Ellipsoid) -> Self {
+        EllipsoidSolver {
+            ellipsoid,
+            accuracy: GeodeticAccuracy::default(),
+        }
+    }
+}
+
+impl GeodesicSolver for SphereSolver {
+    fn direct(&self, prob: &DirectProblem) -> Result<DirectSolution, GeodesyError> {
+        check_lat(prob.x.lat)?;
+        Ok(haversines::direct_on(&self.sphere, prob))
+    }
+
+    fn inverse(&self, prob: &InverseProblem) -> Result<InverseSolution, GeodesyError> {
+        check_lat(prob.x.lat)?;
+        check_lat(prob.y.lat)?;
+        Ok(haversines::inverse_on(&self.sphere, prob))
+    }
+
+    fn radius(&self) -> Radius {
+        self.sphere.radius
+    }
+
+    fn track(&self, prob: &TrackProblem) -> Result<TrackSolution, GeodesyError> {
+        check_lat(prob.x.lat)?;
+        check_lat(prob.y.lat)?;
+        check_lat(prob.p.lat)?;
+        Ok(haversines::track_on(&self.sphere, prob))
+    }
+}
+
+impl GeodesicSolver for EllipsoidSolver {
+    fn direct(&self, prob: &DirectProblem) -> Result<DirectSolution, GeodesyError> {
+        vincenty::direct(&self.ellipsoid, &self.accuracy, prob)
+    }
+
+    fn inverse(&self, prob: &InverseProblem) -> Result<InverseSolution, GeodesyError> {
+        point_to_point::vincenty::inverse(&self.ellipsoid, &self.accuracy, prob)
+    }
+
+    fn radius(&self) -> Radius {
+        self.ellipsoid.mean_r()
+    }
+
+    fn intermediate(
+        &self,
+        prob: &InverseProblem,
+        spacing: Spacing,
+    ) -> Result<Vec<LatLng>, GeodesyError> {
+        let line = GeodesicLine::from_inverse(&self.ellipsoid, &self.accuracy, prob)?;
+        let s = line.length().unwrap_or(Dist { dist: 0.0 });
+        spacing
+            .distances(s)?
+            .into_iter()
+            .map(|s| line.position(s).map(|soln| soln.y))
+            .collect()
+    }
+}
+
+impl GeodesicSolver for karney::Geodesic {
+    fn direct(&self, prob: &DirectProblem) -> Result<DirectSolution, GeodesyError> {
+        karney::Geodesic::direct(self, prob)
+    }
+
+    fn inverse(&self, prob: &InverseProblem) -> Result<InverseSolution, GeodesyError> {
+        karney::Geodesic::inverse(self, prob)
+    }
+
+    fn radius(&self) -> Radius {
+        self.ellipsoid().mean_r()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        earth::ellipsoid::WGS84,
+        earth::sphere::FAI_SPHERE,
+        geodesy::{karney::Geodesic, latlng::LatLng, problems::Az},
+        units::{convert::deg_to_rad, Deg},
+    };
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    #[test]
+    fn azimuth_ranges() {
+        // Swapping solvers keeps the azimuths in 0° .. 360°, here with both
+        // west of north on the way from London to New York.
+        let prob = InverseProblem {
+            x: LatLng::new(51.5007, -0.1246),
+            y: LatLng::new(40.6892, -74.0445),
+        };
+        let geodesic = Geodesic::new(&WGS84).unwrap();
+        let sphere = SphereSolver::new(Sphere::mean(&WGS84));
+        let solvers = [
+            &sphere as &dyn GeodesicSolver,
+            &EllipsoidSolver::new(WGS84),
+            &geodesic,
+        ];
+
+        let range = 0.0..2.0 * PI;
+        let azimuths: Vec<(f64, f64)> = solvers
+            .iter()
+            .map(|solver| {
+                let soln = solver.inverse(&prob).unwrap();
+                (soln.az1.az, soln.az2.unwrap().az)
+            })
+            .collect();
+
+        for &(az1, az2) in &azimuths {
+            assert!(range.contains(&az1) && range.contains(&az2));
+            assert!(az1 > 3.0 * FRAC_PI_2 && az2 > PI && az2 < 3.0 * FRAC_PI_2);
+        }
+        let (sphere_az1, sphere_az2) = azimuths[0];
+        for &(az1, az2) in &azimuths[1..] {
+            assert!((az1 - sphere_az1).abs() < 0.01);
+            assert!((az2 - sphere_az2).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn track_along_equator() {
+        let prob = TrackProblem {
+            x: LatLng::new(0.0, 0.0),
+            y: LatLng::new(0.0, 10.0),
+            p: LatLng::new(1.0, 5.0),
+        };
+
+        for solver in [
+            &Geodesic::new(&WGS84).unwrap() as &dyn GeodesicSolver,
+            &EllipsoidSolver::new(WGS84),
+        ] {
+            let soln = solver.track(&prob).unwrap();
+            let closest = LatLng::new(0.0, 5.0);
+            let miss = solver
+                .inverse(&InverseProblem {
+                    x: soln.closest,
+                    y: closest,
+                })
+                .unwrap();
+
+            // The length of a degree of the meridian at the equator.
+            assert!((soln.cross_track.dist + 110574.389).abs() < 1e-3);
+            assert!(miss.s.dist < 1e-6);
+        }
+    }
+
+    // Solves with haversines but leaves the track to the default iteration.
+    struct IteratedSphere(SphereSolver);
+
+    impl GeodesicSolver for IteratedSphere {
+        fn direct(&self, prob: &DirectProblem) -> Result<DirectSolution, GeodesyError> {
+            self.0.direct(prob)
+        }
+
+        fn inverse(&self, prob: &InverseProblem) -> Result<InverseSolution, GeodesyError> {
+            self.0.inverse(prob)
+        }
+
+        fn radius(&self) -> Radius {
+            self.0.radius()
+        }
+    }
+
+    // The cross-track example of Movable Type, with a cross-track distance of
+    // -307.5m and an along-track distance of 62.331km on the FAI sphere.
+    // SEE: https://www.movable-type.co.uk/scripts/latlong.html#cross-track
+    fn track_problem() -> TrackProblem {
+        TrackProblem {
+            x: LatLng::new(53.3206, -1.7297),
+            y: LatLng::new(53.1887, 0.1334),
+            p: LatLng::new(53.2611, -0.7972),
+        }
+    }
+
+    #[test]
+    fn track_on_sphere() {
+        let prob = track_problem();
+        let fai = SphereSolver::new(FAI_SPHERE).track(&prob).unwrap();
+        assert!((fai.cross_track.dist + 307.5).abs() < 0.05);
+        assert!((fai.along_track.dist - 62331.0).abs() < 0.5);
+
+        // The iteration, with the radius of the sphere, agrees with the closed
+        // form on spheres other than the FAI sphere.
+        for r in [6371000.0, 1737400.0] {
+            let sphere = SphereSolver::new(Sphere {
+                radius: Radius(Meter(r)),
+            });
+            let closed = sphere.track(&prob).unwrap();
+            let iterated = IteratedSphere(sphere).track(&prob).unwrap();
+
+            assert!((iterated.cross_track.dist - closed.cross_track.dist).abs() < 1e-6);
+            assert!((iterated.along_track.dist - closed.along_track.dist).abs() < 1e-6);
+            let miss = sphere
+                .inverse(&InverseProblem {
+                    x: iterated.closest,
+                    y: closed.closest,
+                })
+                .unwrap();
+            assert!(miss.s.dist < 1e-6);
+        }
+    }
+
+    #[test]
+    fn track_on_wgs84() {
+        let prob = track_problem();
+        let geodesic = Geodesic::new(&WGS84).unwrap();
+
+        for solver in [
+            &geodesic as &dyn GeodesicSolver,
+            &EllipsoidSolver::new(WGS84),
+        ] {
+            let TrackSolution {
+                cross_track,
+                along_track,
+                closest,
+                ..
+            } = solver.track(&prob).unwrap();
+
+            // From GeographicLib, by its Rust port geographiclib-rs 0.2.7, as
+            // the point along the geodesic from x where the geodesic to p
+            // meets it at a right angle, found by bisection.
+            // SEE: <https://github.com/georust/geographiclib-rs>
+            assert!((cross_track.dist + 306.844633).abs() < 1e-5);
+            assert!((along_track.dist - 62534.309907).abs() < 1e-5);
+            let expected = LatLng::new(53.2583618810, -0.7977390869);
+            let miss = solver
+                .inverse(&InverseProblem {
+                    x: closest,
+                    y: expected,
+                })
+                .unwrap();
+            assert!(miss.s.dist < 1e-4);
+
+            // The closest point is on the track, along-track from the start.
+            let line = solver
+                .inverse(&InverseProblem {
+                    x: prob.x,
+                    y: prob.y,
+                })
+                .unwrap();
+            let to_closest = solver
+                .inverse(&InverseProblem {
+                    x: prob.x,
+                    y: closest,
+                })
+                .unwrap();
+            assert!((to_closest.s.dist - along_track.dist).abs() < 1e-6);
+            assert!((to_closest.az1.az - line.az1.az).abs() < 1e-9);
+
+            // The geodesic from there to the point meets the track at a right
+            // angle, with a length of the cross-track distance.
+            let across = solver
+                .inverse(&InverseProblem {
+                    x: closest,
+                    y: prob.p,
+                })
+                .unwrap();
+            let angle = across.az1.az - to_closest.az2.unwrap().az;
+            assert!(angle.cos().abs() < 1e-8);
+            assert!((across.s.dist - cross_track.dist.abs()).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn too_many_points() {
+        let solver = EllipsoidSolver::new(WGS84);
+        let prob = InverseProblem {
+            x: LatLng::new(0.0, 0.0),
+            y: LatLng::new(0.5, 179.7),
+        };
+
+        for spacing in [
+            Spacing::Every(Dist { dist: 1e-9 }),
+            Spacing::Count(MAX_POINTS + 1),
+        ] {
+            assert_eq!(
+                solver.intermediate(&prob, spacing).unwrap_err(),
+                GeodesyError::TooManyPoints(MAX_POINTS)
+            );
+        }
+    }
+
+    #[test]
+    #[allow(clippy::excessive_precision)]
+    fn reduced_length_on_wgs84() {
+        // The first test cases of GeographicLib, from its GeodTest data set,
+        // as (φ₁, λ₁, α₁, φ₂, λ₂, s₁₂, m₁₂, M₁₂, M₂₁).
+        // SEE: https://geographiclib.sourceforge.io/C++/doc/geodesic.html#testgeod
+        let cases = [
+            (
+                35.60777,
+                -139.44815,
+                111.098748429560326,
+                -11.17491,
+                -69.95921,
+                8935244.5604818305,
+                6273170.2055303837,
+                0.16606318447386067,
+                0.16479116945612937,
+            ),
+            (
+                55.52454,
+                106.05087,
+                22.020059880982801,
+                77.03196,
+                197.18234,
+                4105086.1713924406,
+                3828869.3344387607,
+                0.80076349608092607,
+                0.80101006984201008,
+            ),
+            (
+                -21.97856,
+                142.59065,
+                -32.44456876433189,
+                41.84138,
+                98.56635,
+                8394328.894657671,
+                6161154.5773110616,
+                0.24816339233950381,
+                0.24930251203627892,
+            ),
+        ];
+        let geodesic = Geodesic::new(&WGS84).unwrap();
+
+        for (lat1, lng1, az1, lat2, lng2, s12, m12, scale12, scale21) in cases {
+            let x = LatLng::new(lat1, lng1);
+            let y = LatLng::new(lat2, lng2);
+            let direct = DirectProblem {
+                x,
+                az1: Az {
+                    az: deg_to_rad(Deg(az1)).0,
+                },
+                s: Dist { dist: s12 },
+            };
+
+            for solver in [
+                &geodesic as &dyn GeodesicSolver,
+                &EllipsoidSolver::new(WGS84),
+            ] {
+                for aux in [
+                    solver.inverse(&InverseProblem { x, y }).unwrap().aux,
+                    solver.direct(&direct).unwrap().aux,
+                ] {
+                    let aux = aux.unwrap();
+                    assert!((aux.m12.dist - m12).abs() < 1e-5);
+                    assert!((aux.scale12 - scale12).abs() < 1e-11);
+                    assert!((aux.scale21 - scale21).abs() < 1e-11);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn midpoint_equidistant() {
+        let x = LatLng::new(51.5007, -0.1246);
+        let y = LatLng::new(40.6892, -74.0445);
+        let geodesic = Geodesic::new(&WGS84).unwrap();
+        let sphere = SphereSolver::new(crate::earth::sphere::FAI_SPHERE);
+
+        for solver in [
+            &geodesic as &dyn GeodesicSolver,
+            &EllipsoidSolver::new(WGS84),
+            &sphere,
+        ] {
+            let m = solver.midpoint(&InverseProblem { x, y }).unwrap();
+            let xm = solver.inverse(&InverseProblem { x, y: m }).unwrap().s.dist;
+            let my = solver.inverse(&InverseProblem { x: m, y }).unwrap().s.dist;
+            assert!((xm - my).abs() < 1e-4);
+        }
+    }
+}
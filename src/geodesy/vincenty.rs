@@ -1,5 +1,5 @@
 use crate::{
-    earth::ellipsoid::{flattening, polar_r, Ellipsoid},
+    earth::ellipsoid::{flattening, is_valid, polar_r, Ellipsoid},
     units::{
         convert::{is_plus_minus_half_pi_rad, plus_minus_pi_rad, rad_to_deg},
         Angle, Meter, Rad, Radius,
@@ -7,8 +7,12 @@ use crate::{
 };
 
 use super::{
+    error::GeodesyError,
+    karney::geodesic_aux,
+    latitude::AuxiliaryLatitudes,
     latlng::LatLng,
-    problems::{Az, DirectProblem, DirectSolution, Dist},
+    point_to_point::vincenty::inverse,
+    problems::{Az, DirectProblem, DirectSolution, Dist, InverseProblem},
 };
 
 #[allow(dead_code)]
@@ -19,6 +23,33 @@ fn sin_sq(x: f64) -> f64 {
 #[derive(Debug, Clone, Copy)]
 pub struct GeodeticAccuracy {
     pub accuracy: f64,
+    /// Iteration stops with an error if the accuracy hasn't been reached after
+    /// this many iterations.
+    pub max_iterations: usize,
+}
+
+impl Default for GeodeticAccuracy {
+    fn default() -> Self {
+        GeodeticAccuracy {
+            accuracy: 1e-12,
+            max_iterations: 200,
+        }
+    }
+}
+
+// Checks that the ellipsoid is valid and that the latitude is in range,
+// normalizing the latitude.
+pub(crate) fn check_problem(ellipsoid: &Ellipsoid, lat: Rad) -> Result<Rad, GeodesyError> {
+    if !is_valid(ellipsoid) {
+        return Err(GeodesyError::InvalidEllipsoid);
+    }
+
+    check_lat(lat)
+}
+
+// Checks that the latitude is in range, normalizing it.
+pub(crate) fn check_lat(lat: Rad) -> Result<Rad, GeodesyError> {
+    is_plus_minus_half_pi_rad(lat).ok_or(GeodesyError::LatitudeOutOfRange(rad_to_deg(lat)))
 }
 
 fn cos2(sigma1: f64, sigma: f64) -> (f64, f64) {
@@ -26,6 +57,8 @@ fn cos2(sigma1: f64, sigma: f64) -> (f64, f64) {
     (x, x * x)
 }
 
+// Iterates the angular distance, σ, until it changes by less than the
+// accuracy, returning σ and the number of iterations taken.
 fn iterate_angular_distance(
     accuracy: &GeodeticAccuracy,
     x_a: f64,
@@ -33,32 +66,37 @@ fn iterate_angular_distance(
     s: f64,
     b: f64,
     sigma1: f64,
-    sigma: f64,
-) -> f64 {
+) -> Result<(f64, usize), GeodesyError> {
     let tolerance = accuracy.accuracy;
-    let (cos2x, cos2xsq) = cos2(sigma1, sigma);
-    let sin_sigma = sigma.sin();
-    let cos_sigma = sigma.cos();
-    let sin_sq_sigma = sin_sigma * sin_sigma;
-
-    let delta_sigma = x_b
-        * sin_sigma
-        * (cos2x
-            + x_b / 4.0
-                * (cos_sigma * (-1.0 + 2.0 * cos2xsq)
-                    - x_b / 6.0 * cos2x * (-3.0 + 4.0 * sin_sq_sigma) * (-3.0 + 4.0 * cos2xsq)));
-
-    let sigma_prime = s / (b * x_a) + delta_sigma;
-    if (sigma - sigma_prime).abs() < tolerance {
-        sigma
-    } else {
-        iterate_angular_distance(accuracy, x_a, x_b, s, b, sigma1, sigma_prime)
+    let mut sigma = s / (b * x_a);
+
+    for i in 1..=accuracy.max_iterations {
+        let (cos2x, cos2xsq) = cos2(sigma1, sigma);
+        let sin_sigma = sigma.sin();
+        let cos_sigma = sigma.cos();
+        let sin_sq_sigma = sin_sigma * sin_sigma;
+
+        let delta_sigma = x_b
+            * sin_sigma
+            * (cos2x
+                + x_b / 4.0
+                    * (cos_sigma * (-1.0 + 2.0 * cos2xsq)
+                        - x_b / 6.0
+                            * cos2x
+                            * (-3.0 + 4.0 * sin_sq_sigma)
+                            * (-3.0 + 4.0 * cos2xsq)));
+
+        let sigma_prime = s / (b * x_a) + delta_sigma;
+        if (sigma - sigma_prime).abs() < tolerance {
+            return Ok((sigma, i));
+        }
+
+        sigma = sigma_prime;
     }
+
+    Err(GeodesyError::IterationLimit(accuracy.max_iterations))
 }
 
-// The solution to the direct geodesy problem with input latitude unchecked and
-// longitude not normalized.
-//
 // Symbol reference from Vincenty's paper.
 // a, b   = major and minor semiaxes of the ellipsoid
 // f      = flattening (a - b) / a
@@ -72,120 +110,252 @@ fn iterate_angular_distance(
 // σ      = angular distance P₁ P₂, on the sphere
 // σ1     = angular distance on the sphere from the equator to P₁
 // σm     = angular distance on the sphere from the equator to the midpoint of the line
-fn direct_unchecked(
-    ellipsoid: &Ellipsoid,
-    accuracy: &GeodeticAccuracy,
-    p: &DirectProblem,
-) -> DirectSolution {
-    let Rad(lat1) = p.x.lat;
-    let Rad(lng1) = p.x.lng;
-    let Az { az: az1 } = p.az1;
-    let Dist { dist: s } = p.s;
-
-    let Radius(Meter(a)) = ellipsoid.equatorial_r;
-    let Radius(Meter(b)) = polar_r(ellipsoid);
-    let f = flattening(ellipsoid);
-
-    // Initial setup
-    let aux_lat = |lat: f64| ((1.0 - f) * lat.tan()).atan();
-    let x_u1: f64 = aux_lat(lat1);
-    let cos_u1 = x_u1.cos();
-    let sin_u1 = x_u1.sin();
-
-    // NOTE: In some transcriptions of Vincenty's formula to code the following
-    // are sometimes seen for calculating U1, cosU1 and sinU1.
-    // let tan_u1 = (1.0 - f) * (lat1).tan();
-    // let cos_u1 = 1.0 / (1.0 + tan_u1 * tan_u1).sqrt();
-    // let sin_u1 = tan_u1 * cos_u1;
-    //
-    // SEE: https://www.purplemath.com/modules/idents.htm
-    // sec(x) = 1 / cos(x)
-    // tan²(x) + 1 = sec²(x)
-    // tan(x) = sin(x) / cos(x)
-    let cos_az1 = az1.cos();
-    let sin_az1 = az1.sin();
-    let sigma1 = (x_u1.tan() / cos_az1).atan();
-
-    let sin_alpha = cos_u1 * sin_az1;
-    let sin_sq_alpha = sin_alpha * sin_alpha;
-    let cos_sq_alpha = 1.0 - sin_sq_alpha;
-
-    let u_sq = cos_sq_alpha * (a * a - b * b) / (b * b);
-
-    let x_a = 1.0 + u_sq / 16384.0 * (4096.0 + u_sq * (-768.0 + u_sq * (320.0 - 175.0 * u_sq)));
-    let x_b = u_sq / 1024.0 * (256.0 + u_sq * (-128.0 + u_sq * (74.0 - 47.0 * u_sq)));
-
-    // Solution
-    let sigma = iterate_angular_distance(accuracy, x_a, x_b, s, b, sigma1, s / (b * x_a));
-
-    let sin_sigma = sigma.sin();
-    let cos_sigma = sigma.cos();
-
-    let v = sin_u1 * cos_sigma + cos_u1 * sin_sigma * cos_az1;
-
-    let (j, j_prime) = {
-        let sin_u1_sin_sigma = sin_u1 * sin_sigma;
-        let cos_u1_cos_sigma_cos_az1 = cos_u1 * cos_sigma * cos_az1;
-        (
-            sin_u1_sin_sigma - cos_u1_cos_sigma_cos_az1,
-            -sin_u1_sin_sigma + cos_u1_cos_sigma_cos_az1,
-        )
-    };
-
-    let w = (1.0 - f) * (sin_sq_alpha + j * j).sqrt();
-    let lat2 = v.atan2(w);
-    let lambda = (sin_sigma * sin_az1).atan2(cos_u1 * cos_sigma - sin_u1 * sin_sigma * cos_az1);
-    let x_c = f / 16.0 * cos_sq_alpha * (4.0 + f * (4.0 - 3.0 * cos_sq_alpha));
-
-    let diff_lng = {
-        let (cos2x, cos2x_sq) = cos2(sigma1, sigma);
-        let y_prime = cos2x + x_c * cos_sigma * (-1.0 + 2.0 * cos2x_sq);
-        let x_prime = sigma + x_c * sin_sigma * y_prime;
-        lambda - (1.0 - x_c) * f * sin_alpha * x_prime
-    };
-
-    let lng2 = diff_lng + lng1;
-
-    DirectSolution {
-        y: LatLng {
-            lat: Rad(lat2),
-            lng: Rad(lng2),
-        },
-        az2: Some(Az {
-            az: sin_alpha.atan2(j_prime),
-        }),
+
+/// A geodesic leaving a point at an azimuth, holding the quantities of
+/// Vincenty's direct solution that don't change along it, U₁, σ₁, sin α and the
+/// coefficients A, B and C, to find any number of points on it.
+///
+/// ```
+/// # use auxillary_sphere::{
+/// #     earth::ellipsoid::WGS84,
+/// #     geodesy::{latlng::LatLng, point_to_point, problems::*, vincenty::*},
+/// #     units::Rad,
+/// # };
+/// let accuracy = GeodeticAccuracy::default();
+/// let x = LatLng::new(51.5007, -0.1246);
+/// let y = LatLng::new(40.6892, -74.0445);
+/// let prob = InverseProblem { x, y };
+/// let line = GeodesicLine::from_inverse(&WGS84, &accuracy, &prob).unwrap();
+/// let soln = point_to_point::vincenty::inverse(&WGS84, &accuracy, &prob).unwrap();
+///
+/// // The end of the line is the second point, reached at its azimuth.
+/// let end = line.position(line.length().unwrap()).unwrap();
+/// assert!((end.y.lat.0 - y.lat.0).abs() < 1e-12);
+/// assert!((end.y.lng.0 - y.lng.0).abs() < 1e-12);
+/// assert!((end.az2.unwrap().az - soln.az2.unwrap().az).abs() < 1e-9);
+///
+/// // The distance to an arc on the auxiliary sphere goes back to that arc.
+/// let sigma = Rad(0.5);
+/// let by_arc = line.arc_position(sigma);
+/// let by_distance = line.position(line.arc_distance(sigma)).unwrap();
+/// assert!((by_arc.y.lat.0 - by_distance.y.lat.0).abs() < 1e-12);
+/// assert!((by_arc.y.lng.0 - by_distance.y.lng.0).abs() < 1e-12);
+///
+/// // Half way along, as with the direct problem.
+/// let s = Dist { dist: soln.s.dist / 2.0 };
+/// let mid = line.position(s).unwrap();
+/// let direct = direct(&WGS84, &accuracy, &DirectProblem { x, az1: soln.az1, s }).unwrap();
+/// assert!((mid.y.lat.0 - direct.y.lat.0).abs() < 1e-12);
+/// assert!((mid.y.lng.0 - direct.y.lng.0).abs() < 1e-12);
+/// assert!((mid.az2.unwrap().az - direct.az2.unwrap().az).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct GeodesicLine {
+    accuracy: GeodeticAccuracy,
+    lng1: f64,
+    b: f64,
+    f: f64,
+    sin_u1: f64,
+    cos_u1: f64,
+    sin_az1: f64,
+    cos_az1: f64,
+    sigma1: f64,
+    sin_alpha: f64,
+    // The second eccentricity squared, (a² - b²) / b².
+    ep2: f64,
+    x_a: f64,
+    x_b: f64,
+    x_c: f64,
+    length: Option<Dist>,
+}
+
+impl GeodesicLine {
+    /// The line from a point at an azimuth, with the latitude rejected outside
+    /// the range -90° .. 90°.
+    pub fn new(
+        ellipsoid: &Ellipsoid,
+        accuracy: &GeodeticAccuracy,
+        x: &LatLng,
+        az1: &Az,
+    ) -> Result<Self, GeodesyError> {
+        let Rad(lat1) = check_problem(ellipsoid, x.lat)?;
+        let u1 = AuxiliaryLatitudes::new(ellipsoid)?.reduced(lat1);
+        let lng1 = plus_minus_pi_rad(x.lng);
+        let az1 = Rad(az1.az).normalize();
+        Ok(GeodesicLine::unchecked(ellipsoid, accuracy, u1, lng1, az1))
+    }
+
+    /// The line through the points of the inverse problem, from the first
+    /// towards the second.
+    pub fn from_inverse(
+        ellipsoid: &Ellipsoid,
+        accuracy: &GeodeticAccuracy,
+        prob: &InverseProblem,
+    ) -> Result<Self, GeodesyError> {
+        let soln = inverse(ellipsoid, accuracy, prob)?;
+        let line = GeodesicLine::new(ellipsoid, accuracy, &prob.x, &soln.az1)?;
+        Ok(GeodesicLine {
+            length: Some(soln.s),
+            ..line
+        })
+    }
+
+    fn unchecked(
+        ellipsoid: &Ellipsoid,
+        accuracy: &GeodeticAccuracy,
+        (sin_u1, cos_u1): (f64, f64),
+        Rad(lng1): Rad,
+        Rad(az1): Rad,
+    ) -> Self {
+        let Radius(Meter(a)) = ellipsoid.equatorial_r;
+        let Radius(Meter(b)) = polar_r(ellipsoid);
+        let f = flattening(ellipsoid);
+
+        let cos_az1 = az1.cos();
+        let sin_az1 = az1.sin();
+        let sigma1 = sin_u1.atan2(cos_u1 * cos_az1);
+
+        let sin_alpha = cos_u1 * sin_az1;
+        let sin_sq_alpha = sin_alpha * sin_alpha;
+        let cos_sq_alpha = 1.0 - sin_sq_alpha;
+
+        let u_sq = cos_sq_alpha * (a * a - b * b) / (b * b);
+
+        let x_a = 1.0 + u_sq / 16384.0 * (4096.0 + u_sq * (-768.0 + u_sq * (320.0 - 175.0 * u_sq)));
+        let x_b = u_sq / 1024.0 * (256.0 + u_sq * (-128.0 + u_sq * (74.0 - 47.0 * u_sq)));
+        let x_c = f / 16.0 * cos_sq_alpha * (4.0 + f * (4.0 - 3.0 * cos_sq_alpha));
+
+        GeodesicLine {
+            accuracy: *accuracy,
+            lng1,
+            b,
+            f,
+            sin_u1,
+            cos_u1,
+            sin_az1,
+            cos_az1,
+            sigma1,
+            sin_alpha,
+            ep2: ellipsoid.second_eccentricity_sq(),
+            x_a,
+            x_b,
+            x_c,
+            length: None,
+        }
+    }
+
+    /// The length of the line between the points of the inverse problem it
+    /// was made from.
+    pub fn length(&self) -> Option<Dist> {
+        self.length
+    }
+
+    /// The position and azimuth, in the direction of travel, at a distance
+    /// along the line, negative for behind the start.
+    pub fn position(&self, Dist { dist: s }: Dist) -> Result<DirectSolution, GeodesyError> {
+        let (sigma, iterations) =
+            iterate_angular_distance(&self.accuracy, self.x_a, self.x_b, s, self.b, self.sigma1)?;
+        Ok(self.at_arc(sigma, iterations))
+    }
+
+    /// The position and azimuth, in the direction of travel, at an angular
+    /// distance σ along the line on the auxiliary sphere, without iteration.
+    pub fn arc_position(&self, Rad(sigma): Rad) -> DirectSolution {
+        self.at_arc(sigma, 0)
+    }
+
+    /// The distance along the line to an angular distance σ on the auxiliary
+    /// sphere.
+    pub fn arc_distance(&self, Rad(sigma): Rad) -> Dist {
+        let x_b = self.x_b;
+        let (cos2x, cos2xsq) = cos2(self.sigma1, sigma);
+        let sin_sigma = sigma.sin();
+        let sin_sq_sigma = sin_sigma * sin_sigma;
+
+        let delta_sigma = x_b
+            * sin_sigma
+            * (cos2x
+                + x_b / 4.0
+                    * (sigma.cos() * (-1.0 + 2.0 * cos2xsq)
+                        - x_b / 6.0
+                            * cos2x
+                            * (-3.0 + 4.0 * sin_sq_sigma)
+                            * (-3.0 + 4.0 * cos2xsq)));
+
+        Dist {
+            dist: self.b * self.x_a * (sigma - delta_sigma),
+        }
+    }
+
+    fn at_arc(&self, sigma: f64, iterations: usize) -> DirectSolution {
+        let GeodesicLine {
+            f,
+            sin_u1,
+            cos_u1,
+            sin_az1,
+            cos_az1,
+            sin_alpha,
+            x_c,
+            ..
+        } = *self;
+        let sin_sq_alpha = sin_alpha * sin_alpha;
+
+        let sin_sigma = sigma.sin();
+        let cos_sigma = sigma.cos();
+
+        let v = sin_u1 * cos_sigma + cos_u1 * sin_sigma * cos_az1;
+
+        let (j, j_prime) = {
+            let sin_u1_sin_sigma = sin_u1 * sin_sigma;
+            let cos_u1_cos_sigma_cos_az1 = cos_u1 * cos_sigma * cos_az1;
+            (
+                sin_u1_sin_sigma - cos_u1_cos_sigma_cos_az1,
+                -sin_u1_sin_sigma + cos_u1_cos_sigma_cos_az1,
+            )
+        };
+
+        let w = (1.0 - f) * (sin_sq_alpha + j * j).sqrt();
+        let lat2 = v.atan2(w);
+        let lambda = (sin_sigma * sin_az1).atan2(cos_u1 * cos_sigma - sin_u1 * sin_sigma * cos_az1);
+
+        let diff_lng = {
+            let (cos2x, cos2x_sq) = cos2(self.sigma1, sigma);
+            let y_prime = cos2x + x_c * cos_sigma * (-1.0 + 2.0 * cos2x_sq);
+            let x_prime = sigma + x_c * sin_sigma * y_prime;
+            lambda - (1.0 - x_c) * f * sin_alpha * x_prime
+        };
+
+        let lng2 = diff_lng + self.lng1;
+
+        DirectSolution {
+            y: LatLng {
+                lat: Rad(lat2),
+                lng: plus_minus_pi_rad(Rad(lng2)),
+            },
+            az2: Some(Az {
+                az: Rad(sin_alpha.atan2(j_prime)).normalize().0,
+            }),
+            iterations,
+            aux: Some(geodesic_aux(
+                self.ep2,
+                self.b,
+                (sin_u1, cos_u1),
+                (sin_az1, cos_az1),
+                sigma,
+            )),
+        }
     }
 }
 
-// The solution to the direct geodesy problem with input latitude rejected
-// outside the range -90° .. 90° and longitude normalized to -180° .. 180°.
-#[allow(dead_code)]
-fn direct(
+/// The solution to the direct geodesy problem with input latitude rejected
+/// outside the range -90° .. 90° and longitude normalized to -180° .. 180°.
+/// The longitude of the solution is normalized to -180° .. 180° and its
+/// azimuth, in the direction P₁ P₂ produced, to 0° .. 360°. For many points on
+/// the same geodesic, a [GeodesicLine] saves repeating the setup.
+pub fn direct(
     ellipsoid: &Ellipsoid,
     accuracy: &GeodeticAccuracy,
     p: &DirectProblem,
-) -> Result<DirectSolution, String> {
-    let Rad(lat) = p.x.lat;
-    match is_plus_minus_half_pi_rad(Rad(lat)) {
-        None => Err(format!(
-            "Latitude of {} is outside -90° .. 90° range",
-            rad_to_deg(p.x.lat)
-        )),
-        Some(n_lat) => {
-            let n_lng = plus_minus_pi_rad(p.x.lng);
-            let n_x = LatLng {
-                lat: n_lat,
-                lng: n_lng,
-            };
-            let n_az = Az {
-                az: Rad(p.az1.az).normalize().0,
-            };
-            let n_p = DirectProblem {
-                x: n_x,
-                az1: n_az,
-                s: p.s,
-            };
-            Ok(direct_unchecked(ellipsoid, accuracy, &n_p))
-        }
-    }
+) -> Result<DirectSolution, GeodesyError> {
+    GeodesicLine::new(ellipsoid, accuracy, &p.x, &p.az1)?.position(p.s)
 }
@@ -1,15 +1,26 @@
 pub mod earth {
     pub mod ellipsoid;
-    pub mod sphere {
-        use super::super::units::{Meter, Radius};
-
-        pub static EARTH_RADIUS: Radius = Radius(Meter(6371000.0));
-    }
+    pub mod sphere;
 }
 pub mod geodesy {
+    pub mod datum;
+    pub mod ecef;
+    pub mod error;
     pub mod haversines;
+    pub mod intersection;
+    pub mod karney;
+    pub mod latitude;
     pub mod latlng;
+    pub mod local;
+    pub mod polygon;
     pub mod problems;
+    pub mod projection {
+        pub mod polar_stereographic;
+        pub mod transverse_mercator;
+        pub mod utm;
+    }
+    pub mod rhumb;
+    pub mod solver;
     pub mod vincenty;
     pub mod published {
         pub mod vincenty1975;
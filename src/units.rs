@@ -175,8 +175,14 @@ impl DMS {
     }
 
     pub fn to_deg(&self) -> Deg {
-        let sign = if self.deg < 0 { -1.0 } else { 1.0 };
-        Deg(sign * (self.deg.abs() as f64 + (self.min as f64 / 60.0) + (self.sec / 3600.0)))
+        // The sign is carried by the first nonzero field, as with from_deg.
+        let sign = if self.deg < 0 || self.min < 0 || self.sec < 0.0 {
+            -1.0
+        } else {
+            1.0
+        };
+        Deg(sign
+            * (self.deg.abs() as f64 + (self.min.abs() as f64 / 60.0) + (self.sec.abs() / 3600.0)))
     }
 
     pub fn diff_dms(x: DMS, y: DMS) -> DMS {
@@ -195,12 +201,12 @@ impl DMS {
             .0
         {
             Self::diff_dms(
+                d,
                 DMS {
                     deg: 360,
                     min: 0,
                     sec: 0.0,
                 },
-                d,
             )
         } else {
             d
@@ -498,5 +504,38 @@ mod tests {
         let deg_minus_169 = Deg(-169.06666666622118);
         assert_eq!(dms_minus_169, DMS::from_deg(deg_minus_169));
         assert_eq!(deg_minus_169, dms_minus_169.to_deg());
+
+        let dms_minus_59 = DMS {
+            deg: 0,
+            min: -59,
+            sec: 53.83076,
+        };
+        let deg_minus_59 = dms_minus_59.to_deg();
+        assert!((deg_minus_59.0 + 59.0 / 60.0 + 53.83076 / 3600.0).abs() < 1e-12);
+        assert_eq!(
+            format!("{:.5}", DMS::from_deg(deg_minus_59)),
+            "-0°59'53.83076\""
+        );
+    }
+
+    #[test]
+    fn abs_diff() {
+        let x = DMS {
+            deg: 10,
+            min: 0,
+            sec: 0.0,
+        };
+        let y = DMS {
+            deg: 9,
+            min: 59,
+            sec: 59.0,
+        };
+        let one_sec = DMS {
+            deg: 0,
+            min: 0,
+            sec: 1.0,
+        };
+        assert!((DMS::abs_diff_dms(x, y).to_deg().0 - one_sec.to_deg().0).abs() < 1e-12);
+        assert!((DMS::abs_diff_dms(y, x).to_deg().0 - one_sec.to_deg().0).abs() < 1e-12);
     }
 }